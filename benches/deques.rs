@@ -0,0 +1,367 @@
+// MIT/Apache2 License
+
+//! Compare `ArrayDeque` and `TinyDeque` against `VecDeque`, `arraydeque` and `heapless::Deque`.
+//!
+//! Run with `cargo bench --features alloc`. Capacities of 16, 100 and 1024 elements are measured;
+//! 100 is there so that wrapping an index cannot be reduced to a bit mask.
+
+use criterion::measurement::WallTime;
+use criterion::{
+    black_box, criterion_group, criterion_main, BenchmarkGroup, BenchmarkId, Criterion,
+};
+use std::collections::VecDeque;
+use tinydeque::{ArrayDeque, TinyDeque};
+use tinyvec::Array;
+
+/// The operations every benchmarked deque supports, all on `u64`s.
+trait Deque {
+    const NAME: &'static str;
+
+    fn with_capacity(capacity: usize) -> Self;
+    fn push_back(&mut self, element: u64);
+    fn push_front(&mut self, element: u64);
+    fn pop_back(&mut self) -> Option<u64>;
+    fn pop_front(&mut self) -> Option<u64>;
+    fn get(&self, index: usize) -> Option<&u64>;
+    fn len(&self) -> usize;
+    fn sum(&self) -> u64;
+    fn extend(&mut self, elements: &[u64]);
+    fn truncate(&mut self, len: usize);
+    fn contains(&self, element: &u64) -> bool;
+}
+
+impl<A: Array<Item = u64>> Deque for ArrayDeque<A> {
+    const NAME: &'static str = "tinydeque::ArrayDeque";
+
+    fn with_capacity(_capacity: usize) -> Self {
+        ArrayDeque::new()
+    }
+    fn push_back(&mut self, element: u64) {
+        ArrayDeque::push_back(self, element);
+    }
+    fn push_front(&mut self, element: u64) {
+        ArrayDeque::push_front(self, element);
+    }
+    fn pop_back(&mut self) -> Option<u64> {
+        ArrayDeque::pop_back(self)
+    }
+    fn pop_front(&mut self) -> Option<u64> {
+        ArrayDeque::pop_front(self)
+    }
+    fn get(&self, index: usize) -> Option<&u64> {
+        ArrayDeque::get(self, index)
+    }
+    fn len(&self) -> usize {
+        ArrayDeque::len(self)
+    }
+    fn sum(&self) -> u64 {
+        self.iter().sum()
+    }
+    fn extend(&mut self, elements: &[u64]) {
+        self.extend_from_slice(elements);
+    }
+    fn truncate(&mut self, len: usize) {
+        ArrayDeque::truncate(self, len);
+    }
+    fn contains(&self, element: &u64) -> bool {
+        ArrayDeque::contains(self, element)
+    }
+}
+
+impl Deque for VecDeque<u64> {
+    const NAME: &'static str = "VecDeque";
+
+    fn with_capacity(capacity: usize) -> Self {
+        VecDeque::with_capacity(capacity)
+    }
+    fn push_back(&mut self, element: u64) {
+        VecDeque::push_back(self, element);
+    }
+    fn push_front(&mut self, element: u64) {
+        VecDeque::push_front(self, element);
+    }
+    fn pop_back(&mut self) -> Option<u64> {
+        VecDeque::pop_back(self)
+    }
+    fn pop_front(&mut self) -> Option<u64> {
+        VecDeque::pop_front(self)
+    }
+    fn get(&self, index: usize) -> Option<&u64> {
+        VecDeque::get(self, index)
+    }
+    fn len(&self) -> usize {
+        VecDeque::len(self)
+    }
+    fn sum(&self) -> u64 {
+        self.iter().sum()
+    }
+    fn extend(&mut self, elements: &[u64]) {
+        Extend::extend(self, elements.iter().copied());
+    }
+    fn truncate(&mut self, len: usize) {
+        VecDeque::truncate(self, len);
+    }
+    fn contains(&self, element: &u64) -> bool {
+        VecDeque::contains(self, element)
+    }
+}
+
+impl<const N: usize> Deque for arraydeque::ArrayDeque<u64, N> {
+    const NAME: &'static str = "arraydeque::ArrayDeque";
+
+    fn with_capacity(_capacity: usize) -> Self {
+        arraydeque::ArrayDeque::new()
+    }
+    fn push_back(&mut self, element: u64) {
+        <arraydeque::ArrayDeque<u64, N>>::push_back(self, element).unwrap();
+    }
+    fn push_front(&mut self, element: u64) {
+        <arraydeque::ArrayDeque<u64, N>>::push_front(self, element).unwrap();
+    }
+    fn pop_back(&mut self) -> Option<u64> {
+        <arraydeque::ArrayDeque<u64, N>>::pop_back(self)
+    }
+    fn pop_front(&mut self) -> Option<u64> {
+        <arraydeque::ArrayDeque<u64, N>>::pop_front(self)
+    }
+    fn get(&self, index: usize) -> Option<&u64> {
+        <arraydeque::ArrayDeque<u64, N>>::get(self, index)
+    }
+    fn len(&self) -> usize {
+        <arraydeque::ArrayDeque<u64, N>>::len(self)
+    }
+    fn sum(&self) -> u64 {
+        self.iter().sum()
+    }
+    fn extend(&mut self, elements: &[u64]) {
+        self.extend_back(elements.iter().copied());
+    }
+    fn truncate(&mut self, len: usize) {
+        while <arraydeque::ArrayDeque<u64, N>>::len(self) > len {
+            <arraydeque::ArrayDeque<u64, N>>::pop_back(self);
+        }
+    }
+    fn contains(&self, element: &u64) -> bool {
+        <arraydeque::ArrayDeque<u64, N>>::contains(self, element)
+    }
+}
+
+impl<const N: usize> Deque for heapless::Deque<u64, N> {
+    const NAME: &'static str = "heapless::Deque";
+
+    fn with_capacity(_capacity: usize) -> Self {
+        heapless::Deque::new()
+    }
+    fn push_back(&mut self, element: u64) {
+        heapless::Deque::push_back(self, element).unwrap();
+    }
+    fn push_front(&mut self, element: u64) {
+        heapless::Deque::push_front(self, element).unwrap();
+    }
+    fn pop_back(&mut self) -> Option<u64> {
+        heapless::Deque::pop_back(self)
+    }
+    fn pop_front(&mut self) -> Option<u64> {
+        heapless::Deque::pop_front(self)
+    }
+    fn get(&self, index: usize) -> Option<&u64> {
+        // heapless has no indexing, so go through the slices as a user would
+        let (front, back) = self.as_slices();
+        front
+            .get(index)
+            .or_else(|| back.get(index.wrapping_sub(front.len())))
+    }
+    fn len(&self) -> usize {
+        heapless::Deque::len(self)
+    }
+    fn sum(&self) -> u64 {
+        self.iter().sum()
+    }
+    fn extend(&mut self, elements: &[u64]) {
+        for &element in elements {
+            heapless::Deque::push_back(self, element).unwrap();
+        }
+    }
+    fn truncate(&mut self, len: usize) {
+        while heapless::Deque::len(self) > len {
+            heapless::Deque::pop_back(self);
+        }
+    }
+    fn contains(&self, element: &u64) -> bool {
+        let (front, back) = self.as_slices();
+        front.contains(element) || back.contains(element)
+    }
+}
+
+/// Fill a deque to capacity, with its contents wrapped around the end of the ring buffer.
+fn wrapped<D: Deque>(capacity: usize) -> D {
+    let mut deque = D::with_capacity(capacity);
+    for i in 0..capacity as u64 / 2 {
+        deque.push_back(i);
+    }
+    for i in capacity as u64 / 2..capacity as u64 {
+        deque.push_front(i);
+    }
+    deque
+}
+
+fn push_pop_back<D: Deque>(group: &mut BenchmarkGroup<'_, WallTime>, capacity: usize) {
+    group.bench_function(BenchmarkId::new(D::NAME, capacity), |b| {
+        let mut deque = D::with_capacity(capacity);
+        b.iter(|| {
+            for i in 0..capacity as u64 {
+                deque.push_back(black_box(i));
+            }
+            while let Some(x) = deque.pop_back() {
+                black_box(x);
+            }
+        });
+    });
+}
+
+fn push_pop_front<D: Deque>(group: &mut BenchmarkGroup<'_, WallTime>, capacity: usize) {
+    group.bench_function(BenchmarkId::new(D::NAME, capacity), |b| {
+        let mut deque = D::with_capacity(capacity);
+        b.iter(|| {
+            for i in 0..capacity as u64 {
+                deque.push_front(black_box(i));
+            }
+            while let Some(x) = deque.pop_front() {
+                black_box(x);
+            }
+        });
+    });
+}
+
+fn fifo<D: Deque>(group: &mut BenchmarkGroup<'_, WallTime>, capacity: usize) {
+    group.bench_function(BenchmarkId::new(D::NAME, capacity), |b| {
+        // keep the queue half full, so the indices wrap around constantly
+        let mut deque = D::with_capacity(capacity);
+        for i in 0..capacity as u64 / 2 {
+            deque.push_back(i);
+        }
+        b.iter(|| {
+            for i in 0..capacity as u64 {
+                deque.push_back(black_box(i));
+                black_box(deque.pop_front());
+            }
+        });
+    });
+}
+
+fn iterate<D: Deque>(group: &mut BenchmarkGroup<'_, WallTime>, capacity: usize) {
+    group.bench_function(BenchmarkId::new(D::NAME, capacity), |b| {
+        let deque: D = wrapped(capacity);
+        b.iter(|| black_box(&deque).sum());
+    });
+}
+
+fn index<D: Deque>(group: &mut BenchmarkGroup<'_, WallTime>, capacity: usize) {
+    group.bench_function(BenchmarkId::new(D::NAME, capacity), |b| {
+        // every access goes through the index wrapping arithmetic
+        let deque: D = wrapped(capacity);
+        b.iter(|| {
+            let deque = black_box(&deque);
+            (0..deque.len())
+                .map(|i| *deque.get(i).unwrap())
+                .sum::<u64>()
+        });
+    });
+}
+
+fn extend_truncate<D: Deque>(group: &mut BenchmarkGroup<'_, WallTime>, capacity: usize) {
+    let elements: Vec<u64> = (0..capacity as u64).collect();
+    group.bench_function(BenchmarkId::new(D::NAME, capacity), |b| {
+        let mut deque = D::with_capacity(capacity);
+        b.iter(|| {
+            deque.extend(black_box(&elements));
+            deque.truncate(black_box(0));
+        });
+    });
+}
+
+fn clone<D: Deque + Clone>(group: &mut BenchmarkGroup<'_, WallTime>, capacity: usize) {
+    group.bench_function(BenchmarkId::new(D::NAME, capacity), |b| {
+        let deque: D = wrapped(capacity);
+        b.iter(|| black_box(&deque).clone());
+    });
+}
+
+fn contains<D: Deque>(group: &mut BenchmarkGroup<'_, WallTime>, capacity: usize) {
+    group.bench_function(BenchmarkId::new(D::NAME, capacity), |b| {
+        let deque: D = wrapped(capacity);
+        b.iter(|| black_box(&deque).contains(black_box(&u64::MAX)));
+    });
+}
+
+macro_rules! bench_deques {
+    ($c:expr, $name:literal, $bench:ident) => {{
+        let mut group = $c.benchmark_group($name);
+        bench_deques!(@capacity group, $bench, 16);
+        bench_deques!(@capacity group, $bench, 100);
+        bench_deques!(@capacity group, $bench, 1024);
+        group.finish();
+    }};
+    (@capacity $group:ident, $bench:ident, $capacity:literal) => {
+        $bench::<ArrayDeque<[u64; $capacity]>>(&mut $group, $capacity);
+        $bench::<VecDeque<u64>>(&mut $group, $capacity);
+        $bench::<arraydeque::ArrayDeque<u64, $capacity>>(&mut $group, $capacity);
+        $bench::<heapless::Deque<u64, $capacity>>(&mut $group, $capacity);
+    };
+}
+
+fn deques(c: &mut Criterion) {
+    bench_deques!(c, "push_pop_back", push_pop_back);
+    bench_deques!(c, "push_pop_front", push_pop_front);
+    bench_deques!(c, "fifo", fifo);
+    bench_deques!(c, "iterate", iterate);
+    bench_deques!(c, "index", index);
+    bench_deques!(c, "extend_truncate", extend_truncate);
+    bench_deques!(c, "clone", clone);
+    bench_deques!(c, "contains", contains);
+}
+
+/// The cost of pushing `2 * N` elements into a `TinyDeque<[u64; N]>`, including the spill onto
+/// the heap halfway through, compared to staying on the stack and to starting on the heap.
+fn spill(c: &mut Criterion) {
+    macro_rules! spill_at {
+        ($group:ident, $capacity:literal) => {
+            $group.bench_function(BenchmarkId::new("stack only", $capacity), |b| {
+                b.iter(|| {
+                    let mut deque: TinyDeque<[u64; $capacity]> = TinyDeque::new();
+                    for i in 0..$capacity {
+                        deque.push_back(black_box(i));
+                    }
+                    deque
+                });
+            });
+            $group.bench_function(BenchmarkId::new("spilled", $capacity), |b| {
+                b.iter(|| {
+                    let mut deque: TinyDeque<[u64; $capacity]> = TinyDeque::new();
+                    for i in 0..2 * $capacity {
+                        deque.push_back(black_box(i));
+                    }
+                    deque
+                });
+            });
+            $group.bench_function(BenchmarkId::new("VecDeque", $capacity), |b| {
+                b.iter(|| {
+                    let mut deque = VecDeque::new();
+                    for i in 0..2 * $capacity {
+                        deque.push_back(black_box(i));
+                    }
+                    deque
+                });
+            });
+        };
+    }
+
+    let mut group = c.benchmark_group("tiny_deque_spill");
+    spill_at!(group, 16);
+    spill_at!(group, 100);
+    spill_at!(group, 1024);
+    group.finish();
+}
+
+criterion_group!(benches, deques, spill);
+criterion_main!(benches);
@@ -1,85 +1,531 @@
 // MIT/Apache2 License
 
-use std::io::{self, prelude::*};
-use tinydeque::ArrayDeque;
-
-fn help() {
-    println!(
-        "
-pb [item] - Push an item onto the back of the stack.
-pf [item] - Push an item onto the back of the stack.
-ob - Pop an item from the back.
-of - Pop an item from the front.
-c - Get capacity.
-l - Get length.
-e - Get whether or not it's empty.
-f - Get whether or not it's full.
-d - Print debug info.
-h - Print this help menu.
-q - Quit."
-    );
+//! A command-driven REPL for `ArrayDeque` and `TinyDeque`.
+//!
+//! ```text
+//! cargo run --example interactive_test --features alloc -- [array|tiny] [CAPACITY] [SCRIPT]
+//! ```
+//!
+//! The deque type defaults to `array` and the capacity defaults to 8. If a script file is given,
+//! its commands are run and echoed instead of reading from standard input, so the output can be
+//! saved as a regression transcript. Blank lines and lines starting with `#` are skipped.
+//!
+//! The scripts in `examples/scripts` are checked in with their expected transcripts, as `.out`
+//! files next to them, and CI diffs the output against those.
+
+use std::{
+    env, fs,
+    io::{self, prelude::*},
+    process,
+};
+use tinydeque::{ArrayDeque, TinyDeque};
+use tinyvec::Array;
+
+const HELP: &str = "
+push_back X (pb)     Push X onto the back.
+push_front X (pf)    Push X onto the front.
+pop_back (ob)        Pop an item from the back.
+pop_front (of)       Pop an item from the front.
+front, back          Print the front or back item.
+get I                Print the item at index I.
+set I X              Replace the item at index I with X.
+insert I X           Insert X at index I.
+remove I             Remove the item at index I.
+swap I J             Swap the items at indices I and J.
+drain [A] [B]        Remove and print the items in A..B (default: everything).
+truncate N           Shorten the deque to N items.
+clear                Remove every item.
+rotate_left N (rl)   Rotate the deque N places to the left.
+rotate_right N (rr)  Rotate the deque N places to the right.
+iter                 Print every item with its index.
+contains X           Tell whether X is in the deque.
+chunks N, windows N  Print the deque split into chunks or windows of N items.
+slices               Print the two slices that make up the deque.
+contiguous           Make the deque contiguous (array only).
+len, capacity (cap)  Print the length or the capacity.
+empty, full          Tell whether the deque is empty or full.
+layout               Print the physical layout.
+help (h)             Print this help menu.
+quit (q)             Quit.
+";
+
+/// The operations the REPL can perform, over every deque type and capacity.
+trait Deque {
+    fn describe(&self) -> String;
+    fn layout(&self) -> String;
+    fn capacity(&self) -> usize;
+    fn len(&self) -> usize;
+    fn is_full(&self) -> bool;
+    fn push_back(&mut self, item: i32) -> Result<(), String>;
+    fn push_front(&mut self, item: i32) -> Result<(), String>;
+    fn pop_back(&mut self) -> Option<i32>;
+    fn pop_front(&mut self) -> Option<i32>;
+    fn get(&self, index: usize) -> Option<i32>;
+    fn set(&mut self, index: usize, item: i32) -> Option<i32>;
+    fn insert(&mut self, index: usize, item: i32) -> Result<(), String>;
+    fn remove(&mut self, index: usize) -> Option<i32>;
+    fn swap(&mut self, i: usize, j: usize);
+    fn drain(&mut self, start: usize, end: usize) -> Vec<i32>;
+    fn truncate(&mut self, len: usize);
+    fn clear(&mut self);
+    fn rotate_left(&mut self, n: usize);
+    fn rotate_right(&mut self, n: usize);
+    fn items(&self) -> Vec<i32>;
+    fn chunks(&self, size: usize) -> Vec<Vec<i32>>;
+    fn windows(&self, size: usize) -> Vec<Vec<i32>>;
+    fn slices(&self) -> (Vec<i32>, Vec<i32>);
+    fn make_contiguous(&mut self) -> Option<Vec<i32>>;
+}
+
+impl<A: Array<Item = i32>> Deque for ArrayDeque<A> {
+    fn describe(&self) -> String {
+        format!("ArrayDeque with capacity {}", ArrayDeque::<A>::capacity())
+    }
+
+    fn layout(&self) -> String {
+        format!("{:?}", self.debug_layout())
+    }
+
+    fn capacity(&self) -> usize {
+        ArrayDeque::<A>::capacity()
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn is_full(&self) -> bool {
+        self.is_full()
+    }
+
+    fn push_back(&mut self, item: i32) -> Result<(), String> {
+        self.try_push_back(item)
+            .map_err(|reject| format!("unable to push {}: {}", reject.element(), reject))
+    }
+
+    fn push_front(&mut self, item: i32) -> Result<(), String> {
+        self.try_push_front(item)
+            .map_err(|reject| format!("unable to push {}: {}", reject.element(), reject))
+    }
+
+    fn pop_back(&mut self) -> Option<i32> {
+        self.pop_back()
+    }
+
+    fn pop_front(&mut self) -> Option<i32> {
+        self.pop_front()
+    }
+
+    fn get(&self, index: usize) -> Option<i32> {
+        self.get(index).copied()
+    }
+
+    fn set(&mut self, index: usize, item: i32) -> Option<i32> {
+        self.get_mut(index)
+            .map(|slot| std::mem::replace(slot, item))
+    }
+
+    fn insert(&mut self, index: usize, item: i32) -> Result<(), String> {
+        self.try_insert(index, item)
+            .map_err(|reject| format!("unable to insert {}: {}", reject.element(), reject))
+    }
+
+    fn remove(&mut self, index: usize) -> Option<i32> {
+        self.remove(index)
+    }
+
+    fn swap(&mut self, i: usize, j: usize) {
+        self.swap(i, j);
+    }
+
+    fn drain(&mut self, start: usize, end: usize) -> Vec<i32> {
+        self.drain(start..end).collect()
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.truncate(len);
+    }
+
+    fn clear(&mut self) {
+        self.clear();
+    }
+
+    fn rotate_left(&mut self, n: usize) {
+        self.rotate_left(n);
+    }
+
+    fn rotate_right(&mut self, n: usize) {
+        self.rotate_right(n);
+    }
+
+    fn items(&self) -> Vec<i32> {
+        self.iter().copied().collect()
+    }
+
+    fn chunks(&self, size: usize) -> Vec<Vec<i32>> {
+        self.chunks(size).map(|chunk| chunk.to_vec()).collect()
+    }
+
+    fn windows(&self, size: usize) -> Vec<Vec<i32>> {
+        self.windows(size).map(|window| window.to_vec()).collect()
+    }
+
+    fn slices(&self) -> (Vec<i32>, Vec<i32>) {
+        let (front, back) = self.as_slices();
+        (front.to_vec(), back.to_vec())
+    }
+
+    fn make_contiguous(&mut self) -> Option<Vec<i32>> {
+        Some(self.make_contiguous().to_vec())
+    }
+}
+
+impl<A: Array<Item = i32>> Deque for TinyDeque<A> {
+    fn describe(&self) -> String {
+        format!("TinyDeque with inline capacity {}", A::CAPACITY)
+    }
+
+    fn layout(&self) -> String {
+        match self {
+            TinyDeque::Stack(s) => format!("Stack {:?}", s.debug_layout()),
+            TinyDeque::Heap(v) => {
+                let (front, back) = v.as_slices();
+                format!(
+                    "Heap {{ capacity: {}, len: {}, slices: ({:?}, {:?}) }}",
+                    v.capacity(),
+                    v.len(),
+                    front,
+                    back
+                )
+            }
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        match self {
+            TinyDeque::Stack(_) => A::CAPACITY,
+            TinyDeque::Heap(v) => v.capacity(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn is_full(&self) -> bool {
+        // a `TinyDeque` spills instead of filling up
+        false
+    }
+
+    fn push_back(&mut self, item: i32) -> Result<(), String> {
+        self.push_back(item);
+        Ok(())
+    }
+
+    fn push_front(&mut self, item: i32) -> Result<(), String> {
+        self.push_front(item);
+        Ok(())
+    }
+
+    fn pop_back(&mut self) -> Option<i32> {
+        self.pop_back()
+    }
+
+    fn pop_front(&mut self) -> Option<i32> {
+        self.pop_front()
+    }
+
+    fn get(&self, index: usize) -> Option<i32> {
+        self.get(index).copied()
+    }
+
+    fn set(&mut self, index: usize, item: i32) -> Option<i32> {
+        self.get_mut(index)
+            .map(|slot| std::mem::replace(slot, item))
+    }
+
+    fn insert(&mut self, index: usize, item: i32) -> Result<(), String> {
+        self.insert(index, item);
+        Ok(())
+    }
+
+    fn remove(&mut self, index: usize) -> Option<i32> {
+        self.remove(index)
+    }
+
+    fn swap(&mut self, i: usize, j: usize) {
+        self.swap(i, j);
+    }
+
+    fn drain(&mut self, start: usize, end: usize) -> Vec<i32> {
+        self.drain(start..end).collect()
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.truncate(len);
+    }
+
+    fn clear(&mut self) {
+        self.clear();
+    }
+
+    fn rotate_left(&mut self, n: usize) {
+        self.rotate_left(n);
+    }
+
+    fn rotate_right(&mut self, n: usize) {
+        self.rotate_right(n);
+    }
+
+    fn items(&self) -> Vec<i32> {
+        self.iter().copied().collect()
+    }
+
+    fn chunks(&self, size: usize) -> Vec<Vec<i32>> {
+        self.chunks(size).map(|chunk| chunk.to_vec()).collect()
+    }
+
+    fn windows(&self, size: usize) -> Vec<Vec<i32>> {
+        self.windows(size).map(|window| window.to_vec()).collect()
+    }
+
+    fn slices(&self) -> (Vec<i32>, Vec<i32>) {
+        let (front, back) = self.as_slices();
+        (front.to_vec(), back.to_vec())
+    }
+
+    fn make_contiguous(&mut self) -> Option<Vec<i32>> {
+        None
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Array,
+    Tiny,
+}
+
+fn new_deque<A: Array<Item = i32> + 'static>(kind: Kind) -> Box<dyn Deque> {
+    match kind {
+        Kind::Array => Box::new(ArrayDeque::<A>::new()),
+        Kind::Tiny => Box::new(TinyDeque::<A>::new()),
+    }
+}
+
+macro_rules! deque_with_capacity {
+    ($kind: expr, $capacity: expr, [$($n: literal),*]) => {
+        match $capacity {
+            $($n => Some(new_deque::<[i32; $n]>($kind)),)*
+            _ => None,
+        }
+    };
+}
+
+/// The outcome of a single command.
+enum Outcome {
+    Continue,
+    Quit,
+}
+
+fn parse<T: std::str::FromStr>(words: &[&str], index: usize, name: &str) -> Result<T, String> {
+    let word = words
+        .get(index)
+        .ok_or_else(|| format!("missing argument {}", name))?;
+    word.parse()
+        .map_err(|_| format!("invalid {}: {:?}", name, word))
+}
+
+fn check_index(deque: &dyn Deque, index: usize) -> Result<usize, String> {
+    if index < deque.len() {
+        Ok(index)
+    } else {
+        Err(format!(
+            "index {} is out of bounds for length {}",
+            index,
+            deque.len()
+        ))
+    }
+}
+
+fn check_amount(deque: &dyn Deque, n: usize) -> Result<usize, String> {
+    if n <= deque.len() {
+        Ok(n)
+    } else {
+        Err(format!("{} is greater than the length {}", n, deque.len()))
+    }
+}
+
+fn check_size(size: usize) -> Result<usize, String> {
+    if size == 0 {
+        Err("size must be greater than zero".to_string())
+    } else {
+        Ok(size)
+    }
+}
+
+fn run(deque: &mut dyn Deque, line: &str) -> Result<Outcome, String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let command = match words.first() {
+        Some(command) => *command,
+        None => return Ok(Outcome::Continue),
+    };
+
+    match command {
+        "push_back" | "pb" => deque.push_back(parse(&words, 1, "X")?)?,
+        "push_front" | "pf" => deque.push_front(parse(&words, 1, "X")?)?,
+        "pop_back" | "ob" => println!("{:?}", deque.pop_back()),
+        "pop_front" | "of" => println!("{:?}", deque.pop_front()),
+        "front" => println!("{:?}", deque.get(0)),
+        "back" => println!(
+            "{:?}",
+            deque.len().checked_sub(1).and_then(|i| deque.get(i))
+        ),
+        "get" => println!("{:?}", deque.get(parse(&words, 1, "I")?)),
+        "set" => {
+            let index = check_index(deque, parse(&words, 1, "I")?)?;
+            println!("{:?}", deque.set(index, parse(&words, 2, "X")?));
+        }
+        "insert" => {
+            let index = check_amount(deque, parse(&words, 1, "I")?)?;
+            deque.insert(index, parse(&words, 2, "X")?)?;
+        }
+        "remove" => println!("{:?}", deque.remove(parse(&words, 1, "I")?)),
+        "swap" => {
+            let i = check_index(deque, parse(&words, 1, "I")?)?;
+            let j = check_index(deque, parse(&words, 2, "J")?)?;
+            deque.swap(i, j);
+        }
+        "drain" => {
+            let start = match words.get(1) {
+                Some(_) => check_amount(deque, parse(&words, 1, "A")?)?,
+                None => 0,
+            };
+            let end = match words.get(2) {
+                Some(_) => check_amount(deque, parse(&words, 2, "B")?)?,
+                None => deque.len(),
+            };
+            if start > end {
+                return Err(format!("range {}..{} is backwards", start, end));
+            }
+            println!("{:?}", deque.drain(start, end));
+        }
+        "truncate" => deque.truncate(parse(&words, 1, "N")?),
+        "clear" => deque.clear(),
+        "rotate_left" | "rl" => deque.rotate_left(check_amount(deque, parse(&words, 1, "N")?)?),
+        "rotate_right" | "rr" => deque.rotate_right(check_amount(deque, parse(&words, 1, "N")?)?),
+        "iter" => {
+            for (index, item) in deque.items().into_iter().enumerate() {
+                println!("{}: {}", index, item);
+            }
+        }
+        "contains" => {
+            let item = parse(&words, 1, "X")?;
+            println!("{}", deque.items().contains(&item));
+        }
+        "chunks" => println!("{:?}", deque.chunks(check_size(parse(&words, 1, "N")?)?)),
+        "windows" => println!("{:?}", deque.windows(check_size(parse(&words, 1, "N")?)?)),
+        "slices" => println!("{:?}", deque.slices()),
+        "contiguous" => match deque.make_contiguous() {
+            Some(items) => println!("{:?}", items),
+            None => return Err("only an ArrayDeque can be made contiguous".to_string()),
+        },
+        "len" => println!("Length: {}", deque.len()),
+        "capacity" | "cap" => println!("Capacity: {}", deque.capacity()),
+        "empty" => println!(
+            "Deque is{} empty",
+            if deque.len() == 0 { "" } else { " not" }
+        ),
+        "full" => println!("Deque is{} full", if deque.is_full() { "" } else { " not" }),
+        "layout" => {}
+        "help" | "h" => {
+            println!("{}", HELP);
+            return Ok(Outcome::Continue);
+        }
+        "quit" | "q" => return Ok(Outcome::Quit),
+        _ => return Err(format!("unrecognized command {:?}, try \"help\"", command)),
+    }
+
+    println!("{}", deque.layout());
+    Ok(Outcome::Continue)
+}
+
+fn usage() -> ! {
+    eprintln!("usage: interactive_test [array|tiny] [CAPACITY] [SCRIPT]");
+    process::exit(2);
 }
 
 fn main() {
-    let mut test_deque: ArrayDeque<[i32; 10]> = ArrayDeque::new();
+    let mut args = env::args().skip(1).peekable();
+
+    let kind = match args.peek().map(String::as_str) {
+        Some("array") => Kind::Array,
+        Some("tiny") => Kind::Tiny,
+        _ => Kind::Array,
+    };
+    if matches!(
+        args.peek().map(String::as_str),
+        Some("array") | Some("tiny")
+    ) {
+        args.next();
+    }
+
+    let capacity = match args.peek().map(|arg| arg.parse::<usize>()) {
+        Some(Ok(capacity)) => {
+            args.next();
+            capacity
+        }
+        _ => 8,
+    };
+    let script = args.next();
+    if args.next().is_some() {
+        usage();
+    }
+
+    let mut deque = deque_with_capacity!(
+        kind,
+        capacity,
+        [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 32, 64]
+    )
+    .unwrap_or_else(|| {
+        eprintln!("unsupported capacity {}, use 0 to 16, 32 or 64", capacity);
+        process::exit(2);
+    });
+
+    println!("{}", deque.describe());
 
-    help();
+    if let Some(script) = script {
+        let script = fs::read_to_string(&script).unwrap_or_else(|err| {
+            eprintln!("unable to read {}: {}", script, err);
+            process::exit(1);
+        });
+
+        for line in script.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            println!("> {}", line);
+            match run(&mut *deque, line) {
+                Ok(Outcome::Continue) => {}
+                Ok(Outcome::Quit) => return,
+                Err(err) => println!("error: {}", err),
+            }
+        }
+        return;
+    }
+
+    println!("Type \"help\" for a list of commands.");
     loop {
         print!("> ");
         io::stdout().flush().unwrap();
         let mut line = String::new();
-        io::stdin().read_line(&mut line).unwrap();
-        if line.is_empty() {
-            continue;
-        }
-        let command = (
-            line.remove(0),
-            if !line.is_empty() {
-                Some(line.remove(0))
-            } else {
-                None
-            },
-        );
-        if line.chars().next() == Some(' ') {
-            line.remove(0);
-            line.pop();
+        if io::stdin().read_line(&mut line).unwrap() == 0 {
+            return;
         }
-        let item = if line.len() > 0 {
-            match line.parse::<i32>() {
-                Ok(s) => s,
-                Err(_) => 0,
-            }
-        } else {
-            0i32
-        };
-
-        match command {
-            ('p', Some('b')) => {
-                if let Err(reject) = test_deque.try_push_back(item) {
-                    println!("Unable to push element onto deque back: {}", reject);
-                }
-            }
-            ('p', Some('f')) => {
-                if let Err(reject) = test_deque.try_push_front(item) {
-                    println!("Unable to push element onto deque front: {}", reject);
-                }
-            }
-            ('o', Some('b')) => println!("{:?}", test_deque.pop_back()),
-            ('o', Some('f')) => println!("{:?}", test_deque.pop_front()),
-            ('c', _) => println!("Capacity: {}", ArrayDeque::<[i32; 10]>::capacity()),
-            ('l', _) => println!("Length: {}", test_deque.len()),
-            ('e', _) => println!(
-                "Deque is{} empty",
-                if test_deque.is_empty() { "" } else { " not" }
-            ),
-            ('f', _) => println!(
-                "Deque is{} full",
-                if test_deque.is_full() { "" } else { " not " }
-            ),
-            ('d', _) => println!("{:?}", &test_deque),
-            ('h', _) => help(),
-            ('q', _) => return,
-            _ => println!("Unrecognized command"),
+
+        match run(&mut *deque, &line) {
+            Ok(Outcome::Continue) => {}
+            Ok(Outcome::Quit) => return,
+            Err(err) => println!("error: {}", err),
         }
     }
 }
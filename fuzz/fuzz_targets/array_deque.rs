@@ -0,0 +1,15 @@
+// MIT/Apache2 License
+
+#![no_main]
+
+#[path = "../../tests/model/mod.rs"]
+mod model;
+
+use libfuzzer_sys::fuzz_target;
+use model::Op;
+
+fuzz_target!(|ops: Vec<Op>| {
+    // a capacity that does not divide evenly into anything interesting, and a full-width one
+    model::check_array_deque::<[u8; 7]>(&ops);
+    model::check_array_deque::<[u8; 16]>(&ops);
+});
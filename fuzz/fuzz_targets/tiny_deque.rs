@@ -0,0 +1,14 @@
+// MIT/Apache2 License
+
+#![no_main]
+
+#[path = "../../tests/model/mod.rs"]
+mod model;
+
+use libfuzzer_sys::fuzz_target;
+use model::Op;
+
+fuzz_target!(|ops: Vec<Op>| {
+    // small enough to spill onto the heap often
+    model::check_tiny_deque::<[u8; 5]>(&ops);
+});
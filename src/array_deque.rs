@@ -1,13 +1,29 @@
 // MIT/Apache2 License
 
 use core::{
+    convert::TryFrom,
+    fmt,
     iter::{FromIterator, FusedIterator},
     mem,
+    ops::RangeBounds,
 };
-use tinyvec::Array;
+use super::{
+    chunks::{Chunks, ChunksExact, Windows},
+    deque_slice::{resolve_range, DequeSlice, DequeSliceMut},
+    error::CapacityError,
+};
+use tinyvec::{Array, ArrayVec};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::io::IoSlice;
 
 /// A deque structure that uses an array as backing storage.
 ///
+/// An array with a capacity of zero is allowed. The deque is then always both empty and full,
+/// so every push fails and every pop returns `None`.
+///
 /// # Example
 ///
 /// ```
@@ -38,10 +54,8 @@ use tinyvec::Array;
 /// // fuck this, I'm out of here
 /// assert_eq!(dmv_line.pop_back(), Some("Larson"));
 /// ```
-#[derive(Debug)]
 pub struct ArrayDeque<A: Array> {
     ring_buffer: A,
-    head: usize,
     tail: usize,
     len: usize,
 }
@@ -53,24 +67,34 @@ impl<A: Array> Default for ArrayDeque<A> {
     }
 }
 
+// Index arithmetic for the ring buffer. `index` must be a valid slot (or zero for an empty ring),
+// and `add` and `sub` must be no larger than the capacity, so a single conditional subtraction
+// is enough to wrap the result. Power-of-two capacities are wrapped with a mask instead. The
+// choice is made in a `const` block, so it is settled at compile time even without optimizations.
+
 #[inline]
-fn wrap_index(index: isize, size: isize) -> usize {
-    let base = index % size;
-    if base < 0 {
-        (size + base) as usize
+fn wrap_add<A: Array>(index: usize, add: usize) -> usize {
+    if const { A::CAPACITY.is_power_of_two() } {
+        index.wrapping_add(add) & (A::CAPACITY - 1)
     } else {
-        base as usize
+        let index = index + add;
+        if index >= A::CAPACITY {
+            index - A::CAPACITY
+        } else {
+            index
+        }
     }
 }
 
 #[inline]
-fn wrap_add(index: usize, add: usize, size: usize) -> usize {
-    wrap_index((index as isize).wrapping_add(add as isize), size as isize)
-}
-
-#[inline]
-fn wrap_sub(index: usize, sub: usize, size: usize) -> usize {
-    wrap_index((index as isize).wrapping_sub(sub as isize), size as isize)
+fn wrap_sub<A: Array>(index: usize, sub: usize) -> usize {
+    if const { A::CAPACITY.is_power_of_two() } {
+        index.wrapping_sub(sub) & (A::CAPACITY - 1)
+    } else if index >= sub {
+        index - sub
+    } else {
+        index + A::CAPACITY - sub
+    }
 }
 
 impl<A: Array> ArrayDeque<A> {
@@ -85,9 +109,34 @@ impl<A: Array> ArrayDeque<A> {
     #[inline]
     #[must_use]
     pub fn new() -> Self {
+        Self::from_array_empty(A::default())
+    }
+
+    /// Create a new, empty `ArrayDeque` that uses the given array as backing storage.
+    ///
+    /// The current contents of the array are treated as empty slots, and are dropped as they
+    /// are overwritten. Unlike [`new`](ArrayDeque::new), this is a `const fn`, so it can be used
+    /// to initialize `static` and `const` items.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use core::cell::RefCell;
+    /// use std::sync::Mutex;
+    /// use tinydeque::ArrayDeque;
+    ///
+    /// // the same pattern works with a critical-section mutex on embedded targets
+    /// static EVENTS: Mutex<RefCell<ArrayDeque<[u8; 16]>>> =
+    ///     Mutex::new(RefCell::new(ArrayDeque::from_array_empty([0; 16])));
+    ///
+    /// EVENTS.lock().unwrap().borrow_mut().push_back(42);
+    /// assert_eq!(EVENTS.lock().unwrap().borrow_mut().pop_front(), Some(42));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn from_array_empty(array: A) -> Self {
         Self {
-            ring_buffer: A::default(),
-            head: 0,
+            ring_buffer: array,
             tail: 0,
             len: 0,
         }
@@ -108,12 +157,6 @@ impl<A: Array> ArrayDeque<A> {
         A::CAPACITY
     }
 
-    /// Helper function to get len.
-    #[inline]
-    fn count(tail: usize, head: usize, size: usize) -> usize {
-        wrap_sub(head, tail, size)
-    }
-
     /// Get the length of this `ArrayDeque`.
     ///
     /// # Example
@@ -175,7 +218,7 @@ impl<A: Array> ArrayDeque<A> {
     ///
     /// # Errors
     ///
-    /// If this `ArrayDeque` is full, this function returns an Err with the rejected element.
+    /// If this `ArrayDeque` is full, this function returns a [`CapacityError`] holding the rejected element.
     ///
     /// # Example
     ///
@@ -202,15 +245,13 @@ impl<A: Array> ArrayDeque<A> {
     /// assert_eq!(i, 10);
     /// ```
     #[inline]
-    pub fn try_push_back(&mut self, element: A::Item) -> Result<(), A::Item> {
+    pub fn try_push_back(&mut self, element: A::Item) -> Result<(), CapacityError<A::Item>> {
         // if we're full, error out
         if self.is_full() {
-            return Err(element);
+            return Err(CapacityError::new(element));
         }
 
-        // increment the head by one
-        let head = self.head;
-        self.head = wrap_add(self.head, 1, Self::capacity());
+        let head = self.head();
         self.ring_buffer.as_slice_mut()[head] = element;
         self.len += 1;
         Ok(())
@@ -228,19 +269,51 @@ impl<A: Array> ArrayDeque<A> {
         }
     }
 
+    /// Push an element onto the back of this `ArrayDeque`, evicting the front element if the
+    /// `ArrayDeque` is full.
+    ///
+    /// The evicted element, if any, is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tinydeque::ArrayDeque;
+    /// let mut last_three: ArrayDeque<[u8; 3]> = ArrayDeque::new();
+    /// assert_eq!(last_three.push_back_overwrite(1), None);
+    /// assert_eq!(last_three.push_back_overwrite(2), None);
+    /// assert_eq!(last_three.push_back_overwrite(3), None);
+    /// assert_eq!(last_three.push_back_overwrite(4), Some(1));
+    /// assert_eq!(last_three.front(), Some(&2));
+    /// ```
+    #[inline]
+    pub fn push_back_overwrite(&mut self, element: A::Item) -> Option<A::Item> {
+        if self.is_full() {
+            if Self::capacity() == 0 {
+                return Some(element);
+            }
+
+            let evicted = self.pop_front();
+            self.push_back(element);
+            evicted
+        } else {
+            self.push_back(element);
+            None
+        }
+    }
+
     /// Push an element onto the front of this `ArrayDeque`.
     ///
     /// # Errors
     ///
-    /// If this `ArrayDeque` is full, this function returns an Err with the rejected element.
+    /// If this `ArrayDeque` is full, this function returns a [`CapacityError`] holding the rejected element.
     #[inline]
-    pub fn try_push_front(&mut self, element: A::Item) -> Result<(), A::Item> {
+    pub fn try_push_front(&mut self, element: A::Item) -> Result<(), CapacityError<A::Item>> {
         // if we're full, error out
         if self.is_full() {
-            return Err(element);
+            return Err(CapacityError::new(element));
         }
 
-        self.tail = wrap_sub(self.tail, 1, Self::capacity());
+        self.tail = wrap_sub::<A>(self.tail, 1);
         self.ring_buffer.as_slice_mut()[self.tail] = element;
         self.len += 1;
         Ok(())
@@ -264,9 +337,9 @@ impl<A: Array> ArrayDeque<A> {
         if self.is_empty() {
             None
         } else {
-            self.head = wrap_sub(self.head, 1, Self::capacity());
             self.len -= 1;
-            Some(mem::take(&mut self.ring_buffer.as_slice_mut()[self.head]))
+            let back = wrap_add::<A>(self.tail, self.len);
+            Some(mem::take(&mut self.ring_buffer.as_slice_mut()[back]))
         }
     }
 
@@ -277,7 +350,7 @@ impl<A: Array> ArrayDeque<A> {
             None
         } else {
             let tail = self.tail;
-            self.tail = wrap_add(self.tail, 1, Self::capacity());
+            self.tail = wrap_add::<A>(self.tail, 1);
             self.len -= 1;
             Some(mem::take(&mut self.ring_buffer.as_slice_mut()[tail]))
         }
@@ -305,7 +378,7 @@ impl<A: Array> ArrayDeque<A> {
         if index < self.len() {
             self.ring_buffer
                 .as_slice()
-                .get(wrap_add(self.tail, index, Self::capacity()))
+                .get(wrap_add::<A>(self.tail, index))
         } else {
             None
         }
@@ -315,7 +388,7 @@ impl<A: Array> ArrayDeque<A> {
     #[inline]
     pub fn get_mut(&mut self, index: usize) -> Option<&mut A::Item> {
         if index < self.len() {
-            let i = wrap_add(self.tail, index, Self::capacity());
+            let i = wrap_add::<A>(self.tail, index);
             self.ring_buffer.as_slice_mut().get_mut(i)
         } else {
             None
@@ -325,187 +398,1399 @@ impl<A: Array> ArrayDeque<A> {
     /// Tell whether or not this `ArrayDeque` is contiguous.
     #[inline]
     pub fn is_contiguous(&self) -> bool {
-        self.tail <= self.head
-    }
-
-    /// Get the contents of this `ArrayDeque` in the form of buffer slices.
-    #[inline]
-    pub fn as_slices(&self) -> (&[A::Item], &[A::Item]) {
-        RingSlices::ring_slices(self.ring_buffer.as_slice(), self.head, self.tail)
+        self.tail + self.len <= Self::capacity()
     }
 
-    /// Get the contents of this `ArrayDeque` in the form of mutable buffer slices.
+    /// Get a value whose `Debug` output shows the physical layout of this `ArrayDeque`.
+    ///
+    /// Every slot of the ring buffer is listed in storage order. Slots that are not part of the
+    /// deque are shown as `_`. The front element is marked with `tail`, and the free slot where
+    /// the next element will be pushed onto the back is marked with `head`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tinydeque::ArrayDeque;
+    ///
+    /// let mut deque: ArrayDeque<[u8; 4]> = ArrayDeque::new();
+    /// deque.push_back(1);
+    /// deque.push_back(2);
+    /// deque.push_front(3);
+    ///
+    /// assert_eq!(format!("{:?}", deque), "[3, 1, 2]");
+    /// assert_eq!(
+    ///     format!("{:?}", deque.debug_layout()),
+    ///     "ArrayDeque { capacity: 4, len: 3, tail: 3, head: 2, \
+    ///      ring: [1, 2, head: _, tail: 3] }",
+    /// );
+    /// ```
     #[inline]
-    pub fn as_mut_slices(&mut self) -> (&mut [A::Item], &mut [A::Item]) {
-        RingSlices::ring_slices(self.ring_buffer.as_slice_mut(), self.head, self.tail)
+    pub fn debug_layout(&self) -> DebugLayout<'_, A> {
+        DebugLayout { deque: self }
     }
 
-    /// Truncate this `ArrayDeque` to a certain size.
+    /// Rearrange the contents of this `ArrayDeque` so that they are stored in a single slice,
+    /// and return that slice.
+    ///
+    /// If the contents are already contiguous, nothing is moved.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tinydeque::ArrayDeque;
+    /// let mut deque: ArrayDeque<[u8; 4]> = (0..4).collect();
+    /// deque.pop_front();
+    /// deque.push_back(4);
+    /// assert!(!deque.is_contiguous());
+    ///
+    /// assert_eq!(deque.make_contiguous(), &[1, 2, 3, 4]);
+    /// assert!(deque.is_contiguous());
+    /// ```
     #[inline]
-    pub fn truncate(&mut self, len: usize) {
-        if len > self.len() {
-            return;
+    pub fn make_contiguous(&mut self) -> &mut [A::Item] {
+        if !self.is_contiguous() {
+            self.rotate_to_front();
         }
 
-        let num_dropped = self.len() - len;
-        let (front, back) = self.as_mut_slices();
-
-        // see how many elements in the front slice we need to drop
-        let front_dropped = core::cmp::min(num_dropped, front.len());
-        front.iter_mut().take(front_dropped).for_each(|f| {
-            mem::take(f);
-        });
-
-        // see how many elements in the back slice we need to drop
-        let back_drop = num_dropped - front_dropped;
-        back.iter_mut().take(back_drop).for_each(|b| {
-            mem::take(b);
-        });
+        let (tail, len) = (self.tail, self.len);
+        &mut self.ring_buffer.as_slice_mut()[tail..tail + len]
+    }
 
-        self.head = wrap_sub(self.head, num_dropped, Self::capacity());
+    /// Move the contents of this `ArrayDeque` so that they start at the front of the array.
+    #[inline]
+    pub(crate) fn rotate_to_front(&mut self) {
+        let tail = self.tail;
+        self.ring_buffer.as_slice_mut().rotate_left(tail);
+        self.tail = 0;
     }
 
-    /// Clear this `ArrayDeque` of all elements.
+    /// The index of the slot just past the back element. For a full deque, this is the tail.
     #[inline]
-    pub fn clear(&mut self) {
-        self.truncate(0);
+    fn head(&self) -> usize {
+        wrap_add::<A>(self.tail, self.len)
     }
 
-    /// Create a new iterator.
+    /// Create an `ArrayDeque` from its raw parts.
+    ///
+    /// The `len` elements of the deque are taken to start at index `tail` of the array and wrap
+    /// around to the start of the array if they run past its end. All other slots are treated
+    /// as empty.
+    ///
+    /// # Errors
+    ///
+    /// If `tail` is not a valid index into the array, or `len` is greater than the capacity,
+    /// the array is returned back.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tinydeque::ArrayDeque;
+    /// let deque = ArrayDeque::from_parts([3, 0, 1, 2], 2, 3).unwrap();
+    /// assert!(deque.iter().copied().eq([1, 2, 3].iter().copied()));
+    ///
+    /// assert!(ArrayDeque::from_parts([0; 4], 4, 1).is_err());
+    /// assert!(ArrayDeque::from_parts([0; 4], 0, 5).is_err());
+    /// ```
     #[inline]
-    pub fn iter(&self) -> Iter<'_, A> {
-        Iter {
-            ring_buffer: self.ring_buffer.as_slice(),
-            tail: self.tail,
-            head: self.tail,
+    pub fn from_parts(array: A, tail: usize, len: usize) -> Result<Self, A> {
+        let tail_valid = tail < A::CAPACITY || (tail == 0 && A::CAPACITY == 0);
+        if !tail_valid || len > A::CAPACITY {
+            return Err(array);
         }
+
+        Ok(Self {
+            ring_buffer: array,
+            tail,
+            len,
+        })
     }
 
-    /// Append another `ArrayDeque` onto the back of one.
+    /// Get the backing array of this `ArrayDeque`.
+    ///
+    /// This only succeeds if the `ArrayDeque` is full and its contents start at the front of the
+    /// array, so that the array holds exactly the contents in order. Otherwise, the
+    /// `ArrayDeque` is returned back. Calling [`make_contiguous`](ArrayDeque::make_contiguous)
+    /// on a full `ArrayDeque` first guarantees success.
     ///
     /// # Errors
     ///
-    /// If the `ArrayDeque`'s contents cannot fit into this one, the Err value is returned.
+    /// If the `ArrayDeque` is not full or not contiguous, it is returned back.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tinydeque::ArrayDeque;
+    /// let mut deque = ArrayDeque::from([1, 2, 3]);
+    /// deque.pop_front();
+    /// deque.push_back(4);
+    ///
+    /// let mut deque = deque.into_inner().unwrap_err();
+    /// deque.make_contiguous();
+    /// assert_eq!(deque.into_inner().unwrap(), [2, 3, 4]);
+    /// ```
     #[inline]
-    pub fn append(&mut self, other: &mut Self) -> Result<(), ()> {
-        if self.len() + other.len() > Self::capacity() {
-            Err(())
+    pub fn into_inner(self) -> Result<A, Self> {
+        if self.is_full() && self.tail == 0 {
+            Ok(self.ring_buffer)
         } else {
-            while let Some(item) = other.pop_front() { self.push_back(item); }
-            Ok(())
+            Err(self)
         }
     }
 
-    /// Get the back item of this `ArrayDeque`.
+    /// Get the backing array of this `ArrayDeque` along with its length, after moving the
+    /// contents to the front of the array.
+    ///
+    /// The first `len` elements of the array are the contents of the `ArrayDeque` in order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tinydeque::ArrayDeque;
+    /// let mut deque: ArrayDeque<[u8; 4]> = ArrayDeque::new();
+    /// deque.push_back(2);
+    /// deque.push_front(1);
+    ///
+    /// let (array, len) = deque.into_array_rotated();
+    /// assert_eq!(&array[..len], &[1, 2]);
+    /// ```
     #[inline]
-    pub fn back(&self) -> Option<&A::Item> { self.get(self.len - 1) }
+    pub fn into_array_rotated(mut self) -> (A, usize) {
+        if self.tail != 0 {
+            self.rotate_to_front();
+        }
 
-    /// Get a mutable reference to the back item of this `ArrayDeque`.
+        (self.ring_buffer, self.len)
+    }
+
+    /// Convert this `ArrayDeque` into an `ArrayVec` holding the same elements.
+    ///
+    /// If the contents already start at the front of the array, nothing is moved.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tinydeque::ArrayDeque;
+    /// let mut deque: ArrayDeque<[u8; 4]> = (1..4).collect();
+    /// deque.push_front(0);
+    /// assert_eq!(deque.into_array_vec().as_slice(), &[0, 1, 2, 3]);
+    /// ```
     #[inline]
-    pub fn back_mut(&mut self) -> Option<&mut A::Item> { self.get_mut(self.len - 1) }
+    pub fn into_array_vec(self) -> ArrayVec<A> {
+        let (array, len) = self.into_array_rotated();
+        ArrayVec::from_array_len(array, len)
+    }
 
-    /// Get the front item of this `ArrayDeque`.
+    /// Get the contents of this `ArrayDeque` in the form of buffer slices.
     #[inline]
-    pub fn front(&self) -> Option<&A::Item> { self.get(0) }
+    pub fn as_slices(&self) -> (&[A::Item], &[A::Item]) {
+        RingSlices::ring_slices(self.ring_buffer.as_slice(), self.tail, self.len)
+    }
 
-    /// Get a mutable reference to the front item of this `ArrayDeque`.
+    /// Get the contents of this `ArrayDeque` in the form of mutable buffer slices.
     #[inline]
-    pub fn front_mut(&mut self) -> Option<&mut A::Item> { self.get_mut(0) }
+    pub fn as_mut_slices(&mut self) -> (&mut [A::Item], &mut [A::Item]) {
+        RingSlices::ring_slices(self.ring_buffer.as_slice_mut(), self.tail, self.len)
+    }
 
-    /// Tell whether or not this deque contains an element.
+    /// Get the contents of this byte `ArrayDeque` as two `IoSlice`s, for vectored writes.
+    ///
+    /// These are the same slices as [`as_slices`](ArrayDeque::as_slices), so the second one is
+    /// empty unless the contents wrap around the end of the array.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::io::Write;
+    /// # use tinydeque::ArrayDeque;
+    /// let mut ring: ArrayDeque<[u8; 4]> = ArrayDeque::new();
+    /// ring.extend_from_slice(b"abc");
+    /// ring.rotate_left(2);
+    ///
+    /// let mut socket = Vec::new();
+    /// let written = socket.write_vectored(&ring.as_io_slices()).unwrap();
+    /// assert_eq!(&socket[..written], b"cab");
+    /// ```
+    #[cfg(feature = "std")]
     #[inline]
-    pub fn contains(&self, item: &A::Item) -> bool where A::Item: PartialEq {
+    pub fn as_io_slices(&self) -> [IoSlice<'_>; 2]
+    where
+        A: Array<Item = u8>,
+    {
         let (front, back) = self.as_slices();
-        front.contains(item) || back.contains(item)
-    } 
-}
+        [IoSlice::new(front), IoSlice::new(back)]
+    }
 
-impl<A: Array> Clone for ArrayDeque<A>
-where
-    A::Item: Clone,
-{
+    /// Get the unused slots of this `ArrayDeque`, in the order they would be filled by
+    /// [`push_back`](ArrayDeque::push_back), as at most two mutable slices.
+    ///
+    /// The slots hold leftover or default values. After writing into the first `n` of them, call
+    /// [`commit_back(n)`](ArrayDeque::commit_back) to add them to the back of the deque.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::io::{IoSliceMut, Read};
+    /// # use tinydeque::ArrayDeque;
+    /// let mut ring: ArrayDeque<[u8; 8]> = ArrayDeque::new();
+    /// ring.extend_from_slice(b"------x");
+    /// while ring.len() > 1 {
+    ///     ring.pop_front();
+    /// }
+    ///
+    /// // the free space wraps around the end of the array
+    /// let mut socket: &[u8] = b"hello world";
+    /// let (front, back) = ring.spare_capacity_mut();
+    /// assert_eq!((front.len(), back.len()), (1, 6));
+    /// let read = socket
+    ///     .read_vectored(&mut [IoSliceMut::new(front), IoSliceMut::new(back)])
+    ///     .unwrap();
+    /// ring.commit_back(read);
+    ///
+    /// assert_eq!(read, 7);
+    /// assert!(ring.is_full());
+    /// assert!(ring.iter().eq(b"xhello w".iter()));
+    /// ```
     #[inline]
-    fn clone(&self) -> Self {
-        self.iter().cloned().collect()
+    pub fn spare_capacity_mut(&mut self) -> (&mut [A::Item], &mut [A::Item]) {
+        let free = Self::capacity() - self.len;
+        let head = self.head();
+        let (before, after) = self.ring_buffer.as_slice_mut().split_at_mut(head);
+        let to_end = free.min(after.len());
+        (&mut after[..to_end], &mut before[..free - to_end])
     }
-}
 
-impl<A: Array> FromIterator<A::Item> for ArrayDeque<A> {
+    /// Add the first `n` slots returned by [`spare_capacity_mut`](ArrayDeque::spare_capacity_mut)
+    /// to the back of this `ArrayDeque`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `n` is greater than the number of unused slots.
     #[inline]
-    fn from_iter<T>(iter: T) -> Self
-    where
-        T: IntoIterator<Item = A::Item>,
-    {
-        let mut ad = ArrayDeque::new();
-        ad.extend(iter);
-        ad
+    pub fn commit_back(&mut self, n: usize) {
+        assert!(
+            n <= Self::capacity() - self.len,
+            "<ArrayDeque> Cannot commit more slots than are unused"
+        );
+        self.len += n;
     }
-}
 
-impl<A: Array> Extend<A::Item> for ArrayDeque<A> {
+    /// Get the contents of this `ArrayDeque` as a single [`DequeSlice`].
     #[inline]
-    fn extend<T>(&mut self, iter: T)
-    where
-        T: IntoIterator<Item = A::Item>,
-    {
-        iter.into_iter().for_each(|item| self.push_back(item));
+    pub fn as_deque_slice(&self) -> DequeSlice<'_, A::Item> {
+        let (front, back) = self.as_slices();
+        DequeSlice::new(front, back)
     }
-}
-
-/// An iterator over `ArrayDeque`s.
-#[derive(Clone)]
-pub struct Iter<'a, A: Array + 'a> {
-    ring_buffer: &'a [A::Item],
-    tail: usize,
-    head: usize,
-}
 
-impl<'a, A: Array> Iterator for Iter<'a, A> {
-    type Item = &'a A::Item;
+    /// Get the contents of this `ArrayDeque` as a single [`DequeSliceMut`].
+    #[inline]
+    pub fn as_deque_slice_mut(&mut self) -> DequeSliceMut<'_, A::Item> {
+        let (front, back) = self.as_mut_slices();
+        DequeSliceMut::new(front, back)
+    }
 
+    /// Get a [`DequeSlice`] over a logical range of this `ArrayDeque`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the range is out of bounds.
     #[inline]
-    fn next(&mut self) -> Option<&'a A::Item> {
-        if self.tail == self.head {
-            None
-        } else {
-            let tail = self.tail;
-            self.tail = wrap_add(self.tail, 1, self.ring_buffer.len());
-            Some(&self.ring_buffer[tail])
-        }
+    pub fn range<R: RangeBounds<usize>>(&self, range: R) -> DequeSlice<'_, A::Item> {
+        self.as_deque_slice().range(range)
     }
 
+    /// Get a [`DequeSliceMut`] over a logical range of this `ArrayDeque`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the range is out of bounds.
     #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let len = ArrayDeque::<A>::count(self.tail, self.head, self.ring_buffer.len());
-        (len, Some(len))
+    pub fn range_mut<R: RangeBounds<usize>>(&mut self, range: R) -> DequeSliceMut<'_, A::Item> {
+        let (start, end) = resolve_range(&range, self.len());
+        let (_, rest) = self.as_deque_slice_mut().split_at_mut(start);
+        rest.split_at_mut(end - start).0
     }
-}
 
-impl<'a, A: Array> DoubleEndedIterator for Iter<'a, A> {
+    /// Remove a logical range of elements from this `ArrayDeque`, returning them as an iterator.
+    ///
+    /// The elements are removed even if the iterator is not consumed.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the range is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tinydeque::ArrayDeque;
+    /// let mut numbers: ArrayDeque<[u32; 6]> = (0..6).collect();
+    /// let mut drained = numbers.drain(1..4);
+    /// assert_eq!(drained.next(), Some(1));
+    /// assert_eq!(drained.as_deque_slice(), &[2, 3][..]);
+    /// drop(drained);
+    ///
+    /// assert!(numbers.iter().copied().eq([0, 4, 5].iter().copied()));
+    /// ```
     #[inline]
-    fn next_back(&mut self) -> Option<&'a A::Item> {
-        if self.tail == self.head {
-            None
-        } else {
-            self.head = wrap_sub(self.head, 1, self.ring_buffer.len());
-            Some(&self.ring_buffer[self.head])
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, A> {
+        let (start, end) = resolve_range(&range, self.len());
+        Drain {
+            deque: self,
+            start,
+            front: start,
+            back: end,
+            end,
         }
     }
-}
 
-impl<'a, A: Array> ExactSizeIterator for Iter<'a, A> {}
+    /// Truncate this `ArrayDeque` to a certain size.
+    #[inline]
+    pub fn truncate(&mut self, len: usize) {
+        if len > self.len() {
+            return;
+        }
 
-impl<A: Array> FusedIterator for Iter<'_, A> {}
+        self.as_deque_slice_mut()
+            .split_at_mut(len)
+            .1
+            .iter_mut()
+            .for_each(|item| {
+                mem::take(item);
+            });
 
-/*
-/// A mutable iterator over an ArrayDeque.
-pub struct IterMut<'a, A: Array + 'a> {
-    ring_buffer: &'a mut [A::Item],
-    tail: usize,
-    head: usize,
-}
+        self.len = len;
+    }
 
-impl<'a, A: Array> Iterator for IterMut<'a, A> {
+    /// Clear this `ArrayDeque` of all elements.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Drop the first `n` elements by moving the tail past them, without shifting anything.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `n` is greater than the length of the `ArrayDeque`.
+    #[cfg(feature = "bytes")]
+    #[inline]
+    pub(crate) fn discard_front(&mut self, n: usize) {
+        assert!(n <= self.len, "<ArrayDeque> Cannot discard more elements than the ArrayDeque holds");
+        self.range_mut(..n).into_iter().for_each(|item| {
+            mem::take(item);
+        });
+        self.tail = wrap_add::<A>(self.tail, n);
+        self.len -= n;
+    }
+
+    /// Create a new iterator.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, A> {
+        Iter {
+            ring_buffer: self.ring_buffer.as_slice(),
+            tail: self.tail,
+            len: self.len,
+        }
+    }
+
+    /// Iterate over `size`-element chunks of this `ArrayDeque`, starting at the front.
+    ///
+    /// Each chunk is yielded as a [`DequeSlice`], since it may cross the wrap point of the ring.
+    /// The last chunk may be shorter than `size`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `size` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tinydeque::ArrayDeque;
+    /// let mut bytes: ArrayDeque<[u8; 4]> = (0..4).collect();
+    /// bytes.pop_front();
+    /// bytes.push_back(4);
+    ///
+    /// // the second chunk straddles the end of the ring
+    /// let mut chunks = bytes.chunks(3);
+    /// assert_eq!(chunks.next().unwrap(), &[1, 2, 3][..]);
+    /// assert_eq!(chunks.next().unwrap(), &[4][..]);
+    ///
+    /// let mut chunks = bytes.chunks(2);
+    /// assert_eq!(chunks.next().unwrap().as_slices(), (&[1, 2][..], &[][..]));
+    /// assert_eq!(chunks.next().unwrap().as_slices(), (&[3][..], &[4][..]));
+    /// ```
+    #[inline]
+    pub fn chunks(&self, size: usize) -> Chunks<'_, A::Item> {
+        Chunks::new(self.as_deque_slice(), size)
+    }
+
+    /// Iterate over `size`-element chunks of this `ArrayDeque`, starting at the front and
+    /// skipping any remainder at the back.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `size` is zero.
+    #[inline]
+    pub fn chunks_exact(&self, size: usize) -> ChunksExact<'_, A::Item> {
+        ChunksExact::new(self.as_deque_slice(), size)
+    }
+
+    /// Iterate over all overlapping `size`-element windows of this `ArrayDeque`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `size` is zero.
+    #[inline]
+    pub fn windows(&self, size: usize) -> Windows<'_, A::Item> {
+        Windows::new(self.as_deque_slice(), size)
+    }
+
+    /// Append another `ArrayDeque` onto the back of one.
+    ///
+    /// # Errors
+    ///
+    /// If the `ArrayDeque`'s contents cannot fit into this one, a [`CapacityError`] is returned
+    /// and both `ArrayDeque`s are left untouched.
+    #[inline]
+    pub fn append(&mut self, other: &mut Self) -> Result<(), CapacityError> {
+        if self.len() + other.len() > Self::capacity() {
+            Err(CapacityError::new(()))
+        } else {
+            while let Some(item) = other.pop_front() { self.push_back(item); }
+            Ok(())
+        }
+    }
+
+    /// Push every element of an iterator onto the back of this `ArrayDeque`, stopping at the
+    /// first element that does not fit.
+    ///
+    /// # Errors
+    ///
+    /// If this `ArrayDeque` fills up, this function returns a [`CapacityError`] holding the
+    /// first rejected element. The rest of the iterator is not consumed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tinydeque::ArrayDeque;
+    /// let mut deque: ArrayDeque<[u8; 3]> = ArrayDeque::new();
+    /// let mut source = 1..=5;
+    /// assert_eq!(deque.try_extend(&mut source).unwrap_err().into_inner(), 4);
+    /// assert_eq!(source.next(), Some(5));
+    /// assert!(deque.is_full());
+    /// ```
+    #[inline]
+    pub fn try_extend<I: IntoIterator<Item = A::Item>>(
+        &mut self,
+        iter: I,
+    ) -> Result<(), CapacityError<A::Item>> {
+        iter.into_iter().try_for_each(|item| self.try_push_back(item))
+    }
+
+    /// Copy every element of a slice onto the back of this `ArrayDeque`.
+    ///
+    /// The elements are copied with at most two `copy_from_slice` calls, one for each side of
+    /// the wrap-around point.
+    ///
+    /// # Errors
+    ///
+    /// If the slice does not fit, a [`CapacityError`] holding the slice is returned and this
+    /// `ArrayDeque` is left untouched.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tinydeque::ArrayDeque;
+    /// let mut deque: ArrayDeque<[u8; 4]> = ArrayDeque::new();
+    /// deque.push_front(1);
+    /// deque.try_extend_from_slice(&[2, 3]).unwrap();
+    /// assert!(deque.try_extend_from_slice(&[4, 5]).is_err());
+    /// assert!(deque.iter().copied().eq(1..=3));
+    /// ```
+    #[inline]
+    pub fn try_extend_from_slice<'a>(
+        &mut self,
+        slice: &'a [A::Item],
+    ) -> Result<(), CapacityError<&'a [A::Item]>>
+    where
+        A::Item: Copy,
+    {
+        if slice.len() > Self::capacity() - self.len {
+            return Err(CapacityError::new(slice));
+        }
+
+        // the free slots start at the head and wrap around to the start of the array
+        let head = self.head();
+        let (to_end, wrapped) = slice.split_at(slice.len().min(Self::capacity() - head));
+        let buffer = self.ring_buffer.as_slice_mut();
+        buffer[head..head + to_end.len()].copy_from_slice(to_end);
+        buffer[..wrapped.len()].copy_from_slice(wrapped);
+        self.len += slice.len();
+        Ok(())
+    }
+
+    /// Copy every element of a slice onto the back of this `ArrayDeque`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the slice does not fit.
+    #[inline]
+    pub fn extend_from_slice(&mut self, slice: &[A::Item])
+    where
+        A::Item: Copy,
+    {
+        if let Err(_) = self.try_extend_from_slice(slice) {
+            panic!("<ArrayDeque> Unable to extend ArrayDeque from slice, since it does not fit");
+        }
+    }
+
+    /// Overwrite the elements of this `ArrayDeque` with the elements of a slice.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the slice does not have the same length as this `ArrayDeque`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tinydeque::ArrayDeque;
+    /// let mut deque = ArrayDeque::from([0, 1, 2]);
+    /// deque.rotate_left(1);
+    /// deque.copy_from_slice(&[7, 8, 9]);
+    /// assert_eq!(deque.as_slices(), (&[7, 8][..], &[9][..]));
+    /// ```
+    #[inline]
+    pub fn copy_from_slice(&mut self, src: &[A::Item])
+    where
+        A::Item: Copy,
+    {
+        self.as_deque_slice_mut().copy_from_slice(src);
+    }
+
+    /// Get the back item of this `ArrayDeque`.
+    #[inline]
+    pub fn back(&self) -> Option<&A::Item> { self.get(self.len.wrapping_sub(1)) }
+
+    /// Get a mutable reference to the back item of this `ArrayDeque`.
+    #[inline]
+    pub fn back_mut(&mut self) -> Option<&mut A::Item> { self.get_mut(self.len.wrapping_sub(1)) }
+
+    /// Get the front item of this `ArrayDeque`.
+    #[inline]
+    pub fn front(&self) -> Option<&A::Item> { self.get(0) }
+
+    /// Get a mutable reference to the front item of this `ArrayDeque`.
+    #[inline]
+    pub fn front_mut(&mut self) -> Option<&mut A::Item> { self.get_mut(0) }
+
+    /// Tell whether or not this deque contains an element.
+    #[inline]
+    pub fn contains(&self, item: &A::Item) -> bool where A::Item: PartialEq {
+        let (front, back) = self.as_slices();
+        front.contains(item) || back.contains(item)
+    } 
+
+    /// Swap the elements at indices `i` and `j`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if either index is out of bounds.
+    #[inline]
+    pub fn swap(&mut self, i: usize, j: usize) {
+        assert!(i < self.len(), "<ArrayDeque> Swap index out of bounds");
+        assert!(j < self.len(), "<ArrayDeque> Swap index out of bounds");
+        let i = wrap_add::<A>(self.tail, i);
+        let j = wrap_add::<A>(self.tail, j);
+        self.ring_buffer.as_slice_mut().swap(i, j);
+    }
+
+    /// Insert an element at the given index, shifting whichever side of the `ArrayDeque` is
+    /// shorter.
+    ///
+    /// # Errors
+    ///
+    /// If this `ArrayDeque` is full, this function returns a [`CapacityError`] holding the rejected element.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `index` is greater than the length of the `ArrayDeque`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tinydeque::ArrayDeque;
+    /// let mut letters: ArrayDeque<[char; 4]> = "abd".chars().collect();
+    /// assert!(letters.try_insert(2, 'c').is_ok());
+    /// assert!(letters.iter().copied().eq("abcd".chars()));
+    /// assert_eq!(letters.try_insert(0, 'z').unwrap_err().into_inner(), 'z');
+    /// ```
+    #[inline]
+    pub fn try_insert(&mut self, index: usize, element: A::Item) -> Result<(), CapacityError<A::Item>> {
+        assert!(index <= self.len(), "<ArrayDeque> Insertion index out of bounds");
+
+        if index < self.len() / 2 {
+            // push onto the front and bubble the element forwards
+            self.try_push_front(element)?;
+            (0..index).for_each(|i| self.swap(i, i + 1));
+        } else {
+            // push onto the back and bubble the element backwards
+            self.try_push_back(element)?;
+            (index..self.len() - 1).rev().for_each(|i| self.swap(i, i + 1));
+        }
+        Ok(())
+    }
+
+    /// Insert an element at the given index.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the `ArrayDeque` is full, or if `index` is greater than the
+    /// length of the `ArrayDeque`.
+    #[inline]
+    pub fn insert(&mut self, index: usize, element: A::Item) {
+        if let Err(_) = self.try_insert(index, element) {
+            panic!("<ArrayDeque> Unable to insert element into ArrayDeque, since it is full");
+        }
+    }
+
+    /// Remove the element at the given index, shifting whichever side of the `ArrayDeque` is
+    /// shorter. Returns `None` if the index is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tinydeque::ArrayDeque;
+    /// let mut letters: ArrayDeque<[char; 4]> = "abcd".chars().collect();
+    /// assert_eq!(letters.remove(1), Some('b'));
+    /// assert_eq!(letters.remove(3), None);
+    /// assert!(letters.iter().copied().eq("acd".chars()));
+    /// ```
+    #[inline]
+    pub fn remove(&mut self, index: usize) -> Option<A::Item> {
+        if index >= self.len() {
+            None
+        } else if index < self.len() / 2 {
+            (0..index).rev().for_each(|i| self.swap(i, i + 1));
+            self.pop_front()
+        } else {
+            (index..self.len() - 1).for_each(|i| self.swap(i, i + 1));
+            self.pop_back()
+        }
+    }
+
+    /// Rotate this `ArrayDeque` `n` places to the left, so that the element at index `n` becomes
+    /// the front element.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `n` is greater than the length of the `ArrayDeque`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tinydeque::ArrayDeque;
+    /// let mut digits: ArrayDeque<[u8; 5]> = (0..4).collect();
+    /// digits.rotate_left(1);
+    /// assert!(digits.iter().copied().eq([1, 2, 3, 0].iter().copied()));
+    /// digits.rotate_right(3);
+    /// assert!(digits.iter().copied().eq([2, 3, 0, 1].iter().copied()));
+    /// ```
+    #[inline]
+    pub fn rotate_left(&mut self, n: usize) {
+        assert!(n <= self.len(), "<ArrayDeque> Rotation amount out of bounds");
+        if n == 0 {
+            return;
+        }
+
+        if self.is_full() {
+            // every slot is in use, so the elements can stay where they are
+            self.tail = wrap_add::<A>(self.tail, n);
+        } else if n <= self.len() - n {
+            for _ in 0..n {
+                if let Some(element) = self.pop_front() {
+                    self.push_back(element);
+                }
+            }
+        } else {
+            for _ in n..self.len() {
+                if let Some(element) = self.pop_back() {
+                    self.push_front(element);
+                }
+            }
+        }
+    }
+
+    /// Rotate this `ArrayDeque` `n` places to the right, so that the element at index
+    /// `len - n` becomes the front element.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `n` is greater than the length of the `ArrayDeque`.
+    #[inline]
+    pub fn rotate_right(&mut self, n: usize) {
+        assert!(n <= self.len(), "<ArrayDeque> Rotation amount out of bounds");
+        self.rotate_left(self.len() - n);
+    }
+
+    /// Get a cursor pointing at the front element of this `ArrayDeque`.
+    ///
+    /// If the `ArrayDeque` is empty, the cursor points at the "ghost" position.
+    #[inline]
+    pub fn cursor_front(&self) -> Cursor<'_, A> {
+        Cursor {
+            index: 0,
+            deque: self,
+        }
+    }
+
+    /// Get a cursor pointing at the back element of this `ArrayDeque`.
+    ///
+    /// If the `ArrayDeque` is empty, the cursor points at the "ghost" position.
+    #[inline]
+    pub fn cursor_back(&self) -> Cursor<'_, A> {
+        Cursor {
+            index: self.len().saturating_sub(1),
+            deque: self,
+        }
+    }
+
+    /// Get a mutable cursor pointing at the front element of this `ArrayDeque`.
+    ///
+    /// If the `ArrayDeque` is empty, the cursor points at the "ghost" position.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tinydeque::ArrayDeque;
+    /// let mut work: ArrayDeque<[u32; 8]> = (1..=5).collect();
+    ///
+    /// // drop the odd jobs and schedule a follow-up after every even one
+    /// let mut cursor = work.cursor_front_mut();
+    /// while let Some(&mut job) = cursor.current() {
+    ///     if job % 2 == 1 {
+    ///         cursor.remove_current();
+    ///     } else {
+    ///         cursor.insert_after(job * 10).unwrap();
+    ///         cursor.move_next();
+    ///         cursor.move_next();
+    ///     }
+    /// }
+    ///
+    /// assert!(work.iter().copied().eq([2, 20, 4, 40].iter().copied()));
+    /// ```
+    #[inline]
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, A> {
+        CursorMut {
+            index: 0,
+            deque: self,
+        }
+    }
+
+    /// Get a mutable cursor pointing at the back element of this `ArrayDeque`.
+    ///
+    /// If the `ArrayDeque` is empty, the cursor points at the "ghost" position.
+    #[inline]
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, A> {
+        CursorMut {
+            index: self.len().saturating_sub(1),
+            deque: self,
+        }
+    }
+}
+
+impl<A: Array> Clone for ArrayDeque<A>
+where
+    A::Item: Clone,
+{
+    /// Clone the contents one half of the ring at a time, keeping them at the same slots.
+    #[inline]
+    fn clone(&self) -> Self {
+        let mut ring_buffer = A::default();
+        let (front, back) = self.as_slices();
+        let buffer = ring_buffer.as_slice_mut();
+        buffer[self.tail..self.tail + front.len()].clone_from_slice(front);
+        buffer[..back.len()].clone_from_slice(back);
+        Self {
+            ring_buffer,
+            tail: self.tail,
+            len: self.len,
+        }
+    }
+}
+
+/// An `ArrayDeque` of `Copy` elements can be embedded in `Copy` types.
+///
+/// # Example
+///
+/// ```
+/// # use tinydeque::ArrayDeque;
+/// #[derive(Clone, Copy)]
+/// struct Keypad {
+///     pressed: ArrayDeque<[u8; 8]>,
+/// }
+///
+/// let mut keypad = Keypad { pressed: ArrayDeque::new() };
+/// keypad.pressed.push_back(b'1');
+/// let saved = keypad;
+/// keypad.pressed.clear();
+/// assert_eq!(saved.pressed.front(), Some(&b'1'));
+/// ```
+impl<A: Array + Copy> Copy for ArrayDeque<A> where A::Item: Copy {}
+
+impl<A: Array> fmt::Debug for ArrayDeque<A>
+where
+    A::Item: fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Bytes can be written into an `ArrayDeque`, which makes it usable as a `write!` target.
+///
+/// A string that does not fit is rejected as a whole, so the deque never holds part of a
+/// character.
+impl<A: Array<Item = u8>> fmt::Write for ArrayDeque<A> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if s.len() > Self::capacity() - self.len() {
+            return Err(fmt::Error);
+        }
+        s.bytes().for_each(|byte| self.push_back(byte));
+        Ok(())
+    }
+}
+
+impl<'a, A: Array> IntoIterator for &'a ArrayDeque<A> {
+    type Item = &'a A::Item;
+    type IntoIter = Iter<'a, A>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a, A> {
+        self.iter()
+    }
+}
+
+impl<A: Array> FromIterator<A::Item> for ArrayDeque<A> {
+    #[inline]
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = A::Item>,
+    {
+        let mut ad = ArrayDeque::new();
+        ad.extend(iter);
+        ad
+    }
+}
+
+impl<A: Array> Extend<A::Item> for ArrayDeque<A> {
+    #[inline]
+    fn extend<T>(&mut self, iter: T)
+    where
+        T: IntoIterator<Item = A::Item>,
+    {
+        iter.into_iter().for_each(|item| self.push_back(item));
+    }
+}
+
+impl<A: Array> From<A> for ArrayDeque<A> {
+    /// Create a full `ArrayDeque` from an array, without copying.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tinydeque::ArrayDeque;
+    /// let deque = ArrayDeque::from([1, 2, 3]);
+    /// assert!(deque.is_full());
+    /// assert_eq!(deque.front(), Some(&1));
+    /// ```
+    #[inline]
+    fn from(array: A) -> Self {
+        Self {
+            ring_buffer: array,
+            tail: 0,
+            len: A::CAPACITY,
+        }
+    }
+}
+
+impl<A: Array> From<ArrayVec<A>> for ArrayDeque<A> {
+    /// Create an `ArrayDeque` from an `ArrayVec`, reusing its storage without copying.
+    #[inline]
+    fn from(vec: ArrayVec<A>) -> Self {
+        let len = vec.len();
+        Self {
+            ring_buffer: vec.into_inner(),
+            tail: 0,
+            len,
+        }
+    }
+}
+
+impl<'a, T: Clone, const N: usize> TryFrom<&'a [T]> for ArrayDeque<[T; N]>
+where
+    [T; N]: Array<Item = T>,
+{
+    type Error = CapacityError<&'a [T]>;
+
+    /// Create an `ArrayDeque` by cloning the elements of a slice.
+    ///
+    /// # Errors
+    ///
+    /// If the slice is longer than the capacity of the `ArrayDeque`, a [`CapacityError`]
+    /// holding the slice is returned.
+    #[inline]
+    fn try_from(slice: &'a [T]) -> Result<Self, Self::Error> {
+        if slice.len() > N {
+            return Err(CapacityError::new(slice));
+        }
+
+        let mut deque = Self::new();
+        deque.ring_buffer.as_slice_mut()[..slice.len()].clone_from_slice(slice);
+        deque.len = slice.len();
+        Ok(deque)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const N: usize> TryFrom<Vec<T>> for ArrayDeque<[T; N]>
+where
+    [T; N]: Array<Item = T>,
+{
+    type Error = CapacityError<Vec<T>>;
+
+    /// Create an `ArrayDeque` by moving the elements out of a `Vec`.
+    ///
+    /// # Errors
+    ///
+    /// If the `Vec` is longer than the capacity of the `ArrayDeque`, a [`CapacityError`]
+    /// holding the `Vec` is returned.
+    #[inline]
+    fn try_from(vec: Vec<T>) -> Result<Self, Self::Error> {
+        if vec.len() > N {
+            Err(CapacityError::new(vec))
+        } else {
+            Ok(vec.into_iter().collect())
+        }
+    }
+}
+
+/// The physical layout of an `ArrayDeque`, as returned by
+/// [`debug_layout`](ArrayDeque::debug_layout).
+pub struct DebugLayout<'a, A: Array + 'a> {
+    deque: &'a ArrayDeque<A>,
+}
+
+impl<A: Array> fmt::Debug for DebugLayout<'_, A>
+where
+    A::Item: fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let deque = self.deque;
+        let capacity = ArrayDeque::<A>::capacity();
+        let head = deque.head();
+        let slots = deque
+            .ring_buffer
+            .as_slice()
+            .iter()
+            .enumerate()
+            .map(|(i, value)| Slot {
+                // the distance from the tail tells whether the slot is in use
+                value: if wrap_sub::<A>(i, deque.tail) < deque.len {
+                    Some(value)
+                } else {
+                    None
+                },
+                tail: i == deque.tail,
+                head: i == head,
+            });
+
+        f.debug_struct("ArrayDeque")
+            .field("capacity", &capacity)
+            .field("len", &deque.len)
+            .field("tail", &deque.tail)
+            .field("head", &head)
+            .field("ring", &SlotList(slots))
+            .finish()
+    }
+}
+
+/// A single slot of a ring buffer, for `DebugLayout`.
+struct Slot<'a, T> {
+    value: Option<&'a T>,
+    tail: bool,
+    head: bool,
+}
+
+impl<T: fmt::Debug> fmt::Debug for Slot<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.value {
+            Some(value) => {
+                if self.tail {
+                    f.write_str("tail: ")?;
+                }
+                value.fmt(f)
+            }
+            // a full deque has no free slot, so its head is not marked
+            None if self.head => f.write_str("head: _"),
+            None => f.write_str("_"),
+        }
+    }
+}
+
+/// The slots of a ring buffer, for `DebugLayout`.
+struct SlotList<I>(I);
+
+impl<'a, T, I> fmt::Debug for SlotList<I>
+where
+    T: fmt::Debug + 'a,
+    I: Iterator<Item = Slot<'a, T>> + Clone,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.0.clone()).finish()
+    }
+}
+
+/// An iterator over `ArrayDeque`s.
+#[derive(Clone)]
+pub struct Iter<'a, A: Array + 'a> {
+    ring_buffer: &'a [A::Item],
+    tail: usize,
+    len: usize,
+}
+
+impl<'a, A: Array> Iterator for Iter<'a, A> {
+    type Item = &'a A::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a A::Item> {
+        if self.len == 0 {
+            None
+        } else {
+            let tail = self.tail;
+            self.tail = wrap_add::<A>(self.tail, 1);
+            self.len -= 1;
+            Some(&self.ring_buffer[tail])
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, A: Array> DoubleEndedIterator for Iter<'a, A> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a A::Item> {
+        if self.len == 0 {
+            None
+        } else {
+            self.len -= 1;
+            Some(&self.ring_buffer[wrap_add::<A>(self.tail, self.len)])
+        }
+    }
+}
+
+impl<A: Array> ExactSizeIterator for Iter<'_, A> {}
+
+impl<A: Array> FusedIterator for Iter<'_, A> {}
+
+/// A draining iterator over a range of an `ArrayDeque`.
+///
+/// When this iterator is dropped, any elements it has not yielded are dropped and the
+/// remaining elements of the deque are shifted to close the gap.
+pub struct Drain<'a, A: Array + 'a> {
+    deque: &'a mut ArrayDeque<A>,
+    start: usize,
+    front: usize,
+    back: usize,
+    end: usize,
+}
+
+impl<A: Array> Drain<'_, A> {
+    /// Get the elements that have not yet been yielded as a [`DequeSlice`].
+    #[inline]
+    #[must_use]
+    pub fn as_deque_slice(&self) -> DequeSlice<'_, A::Item> {
+        self.deque.range(self.front..self.back)
+    }
+}
+
+impl<A: Array> Iterator for Drain<'_, A> {
+    type Item = A::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<A::Item> {
+        if self.front == self.back {
+            None
+        } else {
+            let index = self.front;
+            self.front += 1;
+            self.deque.get_mut(index).map(mem::take)
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<A: Array> DoubleEndedIterator for Drain<'_, A> {
+    #[inline]
+    fn next_back(&mut self) -> Option<A::Item> {
+        if self.front == self.back {
+            None
+        } else {
+            self.back -= 1;
+            self.deque.get_mut(self.back).map(mem::take)
+        }
+    }
+}
+
+impl<A: Array> ExactSizeIterator for Drain<'_, A> {}
+
+impl<A: Array> FusedIterator for Drain<'_, A> {}
+
+impl<A: Array> Drop for Drain<'_, A> {
+    #[inline]
+    fn drop(&mut self) {
+        // move the drained slots to the back, then truncate them away
+        let drained = self.end - self.start;
+        let len = self.deque.len();
+        (self.end..len).for_each(|i| self.deque.swap(i - drained, i));
+        self.deque.truncate(len - drained);
+    }
+}
+
+/// A cursor over an `ArrayDeque`.
+///
+/// A cursor points at an element of the deque, or at a "ghost" position between the back and
+/// the front. Moving past either end of the deque moves the cursor onto the ghost position, and
+/// moving again wraps around to the other end.
+pub struct Cursor<'a, A: Array + 'a> {
+    index: usize,
+    deque: &'a ArrayDeque<A>,
+}
+
+impl<A: Array> Clone for Cursor<'_, A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            index: self.index,
+            deque: self.deque,
+        }
+    }
+}
+
+impl<'a, A: Array> Cursor<'a, A> {
+    /// Get the index of the element the cursor points at, or `None` at the ghost position.
+    #[inline]
+    #[must_use]
+    pub fn index(&self) -> Option<usize> {
+        if self.index < self.deque.len() {
+            Some(self.index)
+        } else {
+            None
+        }
+    }
+
+    /// Move the cursor to the next element, or onto the ghost position from the back element.
+    #[inline]
+    pub fn move_next(&mut self) {
+        self.index = cursor_next(self.index, self.deque.len());
+    }
+
+    /// Move the cursor to the previous element, or onto the ghost position from the front
+    /// element.
+    #[inline]
+    pub fn move_prev(&mut self) {
+        self.index = cursor_prev(self.index, self.deque.len());
+    }
+
+    /// Get the element the cursor points at, or `None` at the ghost position.
+    #[inline]
+    #[must_use]
+    pub fn current(&self) -> Option<&'a A::Item> {
+        self.deque.get(self.index)
+    }
+
+    /// Get the element after the cursor. At the ghost position, this is the front element.
+    #[inline]
+    #[must_use]
+    pub fn peek_next(&self) -> Option<&'a A::Item> {
+        self.deque.get(cursor_next(self.index, self.deque.len()))
+    }
+
+    /// Get the element before the cursor. At the ghost position, this is the back element.
+    #[inline]
+    #[must_use]
+    pub fn peek_prev(&self) -> Option<&'a A::Item> {
+        self.deque.get(cursor_prev(self.index, self.deque.len()))
+    }
+
+    /// Get the front element of the underlying `ArrayDeque`.
+    #[inline]
+    #[must_use]
+    pub fn front(&self) -> Option<&'a A::Item> {
+        self.deque.front()
+    }
+
+    /// Get the back element of the underlying `ArrayDeque`.
+    #[inline]
+    #[must_use]
+    pub fn back(&self) -> Option<&'a A::Item> {
+        self.deque.back()
+    }
+}
+
+/// A mutable cursor over an `ArrayDeque`, which can also insert and remove elements.
+///
+/// See [`Cursor`] for how the cursor moves.
+pub struct CursorMut<'a, A: Array + 'a> {
+    index: usize,
+    deque: &'a mut ArrayDeque<A>,
+}
+
+impl<A: Array> CursorMut<'_, A> {
+    /// Get the index of the element the cursor points at, or `None` at the ghost position.
+    #[inline]
+    #[must_use]
+    pub fn index(&self) -> Option<usize> {
+        if self.index < self.deque.len() {
+            Some(self.index)
+        } else {
+            None
+        }
+    }
+
+    /// Move the cursor to the next element, or onto the ghost position from the back element.
+    #[inline]
+    pub fn move_next(&mut self) {
+        self.index = cursor_next(self.index, self.deque.len());
+    }
+
+    /// Move the cursor to the previous element, or onto the ghost position from the front
+    /// element.
+    #[inline]
+    pub fn move_prev(&mut self) {
+        self.index = cursor_prev(self.index, self.deque.len());
+    }
+
+    /// Get the element the cursor points at, or `None` at the ghost position.
+    #[inline]
+    pub fn current(&mut self) -> Option<&mut A::Item> {
+        self.deque.get_mut(self.index)
+    }
+
+    /// Get the element after the cursor. At the ghost position, this is the front element.
+    #[inline]
+    pub fn peek_next(&mut self) -> Option<&mut A::Item> {
+        let next = cursor_next(self.index, self.deque.len());
+        self.deque.get_mut(next)
+    }
+
+    /// Get the element before the cursor. At the ghost position, this is the back element.
+    #[inline]
+    pub fn peek_prev(&mut self) -> Option<&mut A::Item> {
+        let prev = cursor_prev(self.index, self.deque.len());
+        self.deque.get_mut(prev)
+    }
+
+    /// Get a read-only cursor pointing at the same position as this one.
+    #[inline]
+    #[must_use]
+    pub fn as_cursor(&self) -> Cursor<'_, A> {
+        Cursor {
+            index: self.index,
+            deque: self.deque,
+        }
+    }
+
+    /// Insert an element after the cursor. At the ghost position, the element becomes the new
+    /// front element.
+    ///
+    /// # Errors
+    ///
+    /// If the `ArrayDeque` is full, this function returns a [`CapacityError`] holding the rejected element.
+    #[inline]
+    pub fn insert_after(&mut self, element: A::Item) -> Result<(), CapacityError<A::Item>> {
+        if self.index < self.deque.len() {
+            self.deque.try_insert(self.index + 1, element)
+        } else {
+            self.deque.try_push_front(element)?;
+            self.index = self.deque.len();
+            Ok(())
+        }
+    }
+
+    /// Insert an element before the cursor. At the ghost position, the element becomes the new
+    /// back element.
+    ///
+    /// # Errors
+    ///
+    /// If the `ArrayDeque` is full, this function returns a [`CapacityError`] holding the rejected element.
+    #[inline]
+    pub fn insert_before(&mut self, element: A::Item) -> Result<(), CapacityError<A::Item>> {
+        self.deque.try_insert(self.index, element)?;
+        self.index += 1;
+        Ok(())
+    }
+
+    /// Remove the element the cursor points at and move the cursor to the next element.
+    /// Returns `None` at the ghost position.
+    #[inline]
+    pub fn remove_current(&mut self) -> Option<A::Item> {
+        self.deque.remove(self.index)
+    }
+
+    /// Get the front element of the underlying `ArrayDeque`.
+    #[inline]
+    pub fn front(&mut self) -> Option<&mut A::Item> {
+        self.deque.front_mut()
+    }
+
+    /// Get the back element of the underlying `ArrayDeque`.
+    #[inline]
+    pub fn back(&mut self) -> Option<&mut A::Item> {
+        self.deque.back_mut()
+    }
+}
+
+/// The cursor position after `index` in a deque of length `len`, where `len` is the ghost.
+#[inline]
+pub(crate) fn cursor_next(index: usize, len: usize) -> usize {
+    if index >= len {
+        0
+    } else {
+        index + 1
+    }
+}
+
+/// The cursor position before `index` in a deque of length `len`, where `len` is the ghost.
+#[inline]
+pub(crate) fn cursor_prev(index: usize, len: usize) -> usize {
+    if index == 0 || index > len {
+        len
+    } else {
+        index - 1
+    }
+}
+
+/*
+/// A mutable iterator over an ArrayDeque.
+pub struct IterMut<'a, A: Array + 'a> {
+    ring_buffer: &'a mut [A::Item],
+    tail: usize,
+    head: usize,
+}
+
+impl<'a, A: Array> Iterator for IterMut<'a, A> {
     type Item = &'a mut A::Item;
 
     #[inline]
@@ -550,17 +1835,17 @@ impl<A: Array> FusedIterator for IterMut<'_, A> {}
 trait RingSlices: Sized {
     fn slice(self, from: usize, to: usize) -> Self;
     fn split_at(self, i: usize) -> (Self, Self);
+    fn len(&self) -> usize;
 
     #[inline]
-    fn ring_slices(buf: Self, head: usize, tail: usize) -> (Self, Self) {
-        let contiguous = tail <= head;
-        if contiguous {
-            let (empty, buf) = buf.split_at(0);
-            (buf.slice(tail, head), empty)
+    fn ring_slices(buf: Self, tail: usize, len: usize) -> (Self, Self) {
+        let (left, right) = buf.split_at(tail);
+        let right_len = right.len();
+        if len <= right_len {
+            let (empty, right) = right.split_at(0);
+            (right.slice(0, len), empty)
         } else {
-            let (mid, right) = buf.split_at(tail);
-            let (left, _) = mid.split_at(head);
-            (right, left)
+            (right, left.slice(0, len - right_len))
         }
     }
 }
@@ -574,6 +1859,10 @@ impl<T> RingSlices for &[T] {
     fn split_at(self, i: usize) -> (Self, Self) {
         (*self).split_at(i)
     }
+    #[inline]
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
 }
 
 impl<T> RingSlices for &mut [T] {
@@ -585,16 +1874,155 @@ impl<T> RingSlices for &mut [T] {
     fn split_at(self, i: usize) -> (Self, Self) {
         (*self).split_at_mut(i)
     }
+    #[inline]
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+}
+
+#[test]
+fn test_cursor_wrap() {
+    let mut deque: ArrayDeque<[i32; 4]> = ArrayDeque::new();
+    deque.push_back(2);
+    deque.push_back(3);
+    deque.push_front(1);
+
+    let mut cursor = deque.cursor_back();
+    assert_eq!(cursor.current(), Some(&3));
+    cursor.move_next();
+    assert_eq!(cursor.index(), None);
+    assert_eq!(cursor.peek_next(), Some(&1));
+    assert_eq!(cursor.peek_prev(), Some(&3));
+    cursor.move_next();
+    assert_eq!(cursor.current(), Some(&1));
+    cursor.move_prev();
+    cursor.move_prev();
+    assert_eq!(cursor.current(), Some(&3));
+
+    let mut cursor = deque.cursor_front_mut();
+    cursor.move_prev();
+    assert_eq!(cursor.insert_before(4), Ok(()));
+    assert_eq!(cursor.insert_after(0), Err(CapacityError::new(0)));
+    cursor.move_prev();
+    assert_eq!(cursor.remove_current(), Some(4));
+    assert_eq!(cursor.index(), None);
+    assert!(deque.iter().copied().eq(1..=3));
+}
+
+#[test]
+fn test_drain_wrap() {
+    let mut deque: ArrayDeque<[i32; 5]> = (0..5).collect();
+    deque.pop_front();
+    deque.pop_front();
+    deque.push_back(5);
+    deque.push_back(6);
+
+    assert!(deque.drain(1..3).eq(3..5));
+    assert!(deque.iter().copied().eq([2, 5, 6].iter().copied()));
+    assert_eq!(deque.drain(..).next_back(), Some(6));
+    assert!(deque.is_empty());
+}
+
+#[test]
+fn test_conversions_wrap() {
+    let mut deque = ArrayDeque::from([1, 2, 3, 4]);
+    deque.pop_front();
+    deque.push_back(5);
+    assert_eq!(deque.as_slices(), (&[2, 3, 4][..], &[5][..]));
+
+    let vec = deque.into_array_vec();
+    assert_eq!(vec.as_slice(), &[2, 3, 4, 5]);
+    assert!(ArrayDeque::from(vec).iter().eq(deque.iter()));
+
+    let short = ArrayDeque::<[i32; 4]>::try_from(&[7, 8][..]).unwrap();
+    assert_eq!(short.as_slices(), (&[7, 8][..], &[][..]));
+    assert!(ArrayDeque::<[i32; 1]>::try_from(&[7, 8][..]).is_err());
 }
 
 #[test]
 fn test_index_wrap() {
-    assert_eq!(wrap_index(1, 10), 1);
-    assert_eq!(wrap_index(13, 10), 3);
-    assert_eq!(wrap_index(10, 10), 0);
+    // capacities that are not a power of two subtract the capacity at most once
+    assert_eq!(wrap_add::<[u8; 10]>(9, 2), 1);
+    assert_eq!(wrap_add::<[u8; 10]>(5, 2), 7);
+    assert_eq!(wrap_add::<[u8; 10]>(3, 10), 3);
+    assert_eq!(wrap_sub::<[u8; 10]>(1, 6), 5, "subtraction test");
+
+    // powers of two are masked
+    assert_eq!(wrap_add::<[u8; 8]>(7, 2), 1);
+    assert_eq!(wrap_add::<[u8; 8]>(3, 8), 3);
+    assert_eq!(wrap_sub::<[u8; 8]>(1, 6), 3);
+    assert_eq!(wrap_sub::<[u8; 8]>(0, 8), 0);
+}
+
+#[test]
+fn test_debug_layout_full() {
+    use core::fmt::Write;
 
-    assert_eq!(wrap_add(9, 2, 10), 1);
-    assert_eq!(wrap_add(5, 2, 10), 7);
+    let mut deque: ArrayDeque<[u8; 3]> = ArrayDeque::new();
+    write!(deque, "ab").unwrap();
+    assert_eq!(deque.pop_front(), Some(b'a'));
+    write!(deque, "cd").unwrap();
+    assert!(deque.write_str("e").is_err());
 
-    assert_eq!(wrap_sub(1, 6, 10), 5, "subtraction test");
+    let mut layout = ArrayDeque::<[u8; 96]>::new();
+    write!(layout, "{:?}", deque.debug_layout()).unwrap();
+    let (front, back) = layout.as_slices();
+    assert_eq!(back, b"");
+    assert_eq!(
+        front,
+        &b"ArrayDeque { capacity: 3, len: 3, tail: 1, head: 1, ring: [100, tail: 98, 99] }"[..]
+    );
+}
+
+#[test]
+fn test_zero_and_one_slot() {
+    use core::fmt::Write;
+
+    // with no slots, the deque is always both empty and full
+    let mut none: ArrayDeque<[u8; 0]> = ArrayDeque::new();
+    assert!(none.is_empty() && none.is_full());
+    assert_eq!(none.try_push_back(1).unwrap_err().into_inner(), 1);
+    assert_eq!(none.try_push_front(2).unwrap_err().into_inner(), 2);
+    assert_eq!(none.try_insert(0, 3).unwrap_err().into_inner(), 3);
+    assert_eq!(none.push_back_overwrite(4), Some(4));
+    assert!(none.try_extend(Some(5)).is_err());
+    assert!(none.cursor_front_mut().insert_after(6).is_err());
+    assert!(none.write_str("").is_ok() && none.write_str("7").is_err());
+    assert!(none.append(&mut ArrayDeque::new()).is_ok());
+
+    assert_eq!((none.pop_front(), none.pop_back(), none.remove(0)), (None, None, None));
+    assert_eq!((none.get(0), none.front(), none.back()), (None, None, None));
+    assert_eq!(none.as_slices(), (&[][..], &[][..]));
+    assert!(none.is_contiguous() && none.make_contiguous().is_empty());
+    none.rotate_left(0);
+    none.rotate_right(0);
+    none.truncate(0);
+    assert_eq!(none.drain(..).count(), 0);
+    assert_eq!(none.iter().count() + none.chunks(1).count() + none.windows(1).count(), 0);
+
+    let mut layout = ArrayDeque::<[u8; 64]>::new();
+    write!(layout, "{:?}", none.debug_layout()).unwrap();
+    assert_eq!(
+        layout.as_slices().0,
+        &b"ArrayDeque { capacity: 0, len: 0, tail: 0, head: 0, ring: [] }"[..]
+    );
+    assert!(ArrayDeque::from_parts([0u8; 0], 0, 0).is_ok());
+    assert_eq!(none.into_array_rotated(), ([], 0));
+    assert_eq!(none.into_inner().unwrap(), []);
+
+    // with one slot, every push and pop wraps around
+    let mut one: ArrayDeque<[u8; 1]> = ArrayDeque::new();
+    for i in 0..3 {
+        one.push_front(i);
+        assert!(one.is_full());
+        assert_eq!(one.as_slices(), (&[i][..], &[][..]));
+        assert_eq!(one.try_insert(1, 9).unwrap_err().into_inner(), 9);
+        assert_eq!(one.push_back_overwrite(i + 10), Some(i));
+        one.rotate_left(1);
+        one.rotate_right(1);
+        one.swap(0, 0);
+        assert_eq!((one.front(), one.back()), (Some(&(i + 10)), Some(&(i + 10))));
+        assert_eq!(one.pop_back(), Some(i + 10));
+        assert!(one.is_empty());
+    }
 }
@@ -0,0 +1,126 @@
+// MIT/Apache2 License
+
+//! `bytes::Buf` implementations, so codecs can read from byte deques without copying. The
+//! matching `BufMut` implementations live in `buf_mut`.
+
+#![cfg(feature = "bytes")]
+
+use super::array_deque::ArrayDeque;
+#[cfg(feature = "alloc")]
+use super::tiny_deque::TinyDeque;
+use bytes::Buf;
+#[cfg(feature = "std")]
+use std::io::IoSlice;
+use tinyvec::Array;
+
+/// The first non-empty slice of a deque.
+#[inline]
+fn first_chunk<'a>((front, back): (&'a [u8], &'a [u8])) -> &'a [u8] {
+    if front.is_empty() {
+        back
+    } else {
+        front
+    }
+}
+
+/// Fill `dst` with the non-empty slices of a deque, returning how many were written.
+#[cfg(feature = "std")]
+#[inline]
+fn fill_io_slices<'a>((front, back): (&'a [u8], &'a [u8]), dst: &mut [IoSlice<'a>]) -> usize {
+    let slices = [front, back];
+    let filled = slices.iter().filter(|slice| !slice.is_empty());
+    dst.iter_mut()
+        .zip(filled)
+        .map(|(slot, slice)| *slot = IoSlice::new(slice))
+        .count()
+}
+
+/// Reading from a byte `ArrayDeque` consumes bytes from its front.
+///
+/// # Example
+///
+/// ```
+/// use bytes::Buf;
+/// use tinydeque::ArrayDeque;
+///
+/// let mut frames: ArrayDeque<[u8; 8]> = ArrayDeque::new();
+/// frames.extend_from_slice(b"\x00\x03abc");
+/// assert_eq!(frames.get_u16(), 3);
+/// frames.advance(3);
+///
+/// // the next frame wraps around the end of the array
+/// frames.extend_from_slice(b"\x00\x04wxyz");
+/// assert_eq!(frames.chunk(), b"\x00\x04w");
+/// assert_eq!(frames.get_u16(), 4);
+/// assert_eq!(&frames.copy_to_bytes(4)[..], b"wxyz");
+/// assert!(!frames.has_remaining());
+/// ```
+impl<A: Array<Item = u8>> Buf for ArrayDeque<A> {
+    #[inline]
+    fn remaining(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn chunk(&self) -> &[u8] {
+        first_chunk(self.as_slices())
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn chunks_vectored<'a>(&'a self, dst: &mut [IoSlice<'a>]) -> usize {
+        fill_io_slices(self.as_slices(), dst)
+    }
+
+    #[inline]
+    fn advance(&mut self, cnt: usize) {
+        self.discard_front(cnt);
+    }
+}
+
+/// Reading from a byte `TinyDeque` consumes bytes from its front, whether it has spilled or not.
+#[cfg(feature = "alloc")]
+impl<A: Array<Item = u8>> Buf for TinyDeque<A> {
+    #[inline]
+    fn remaining(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn chunk(&self) -> &[u8] {
+        first_chunk(self.as_slices())
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn chunks_vectored<'a>(&'a self, dst: &mut [IoSlice<'a>]) -> usize {
+        fill_io_slices(self.as_slices(), dst)
+    }
+
+    #[inline]
+    fn advance(&mut self, cnt: usize) {
+        match self {
+            Self::Stack(s) => s.discard_front(cnt),
+            Self::Heap(v) => v.advance(cnt),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_buf_chunks_across_wrap() {
+    let mut deque: ArrayDeque<[u8; 4]> = ArrayDeque::new();
+    deque.extend_from_slice(&[1, 2, 3]);
+    deque.advance(2);
+    deque.extend_from_slice(&[4, 5]);
+
+    let mut slices = [IoSlice::new(&[]); 3];
+    assert_eq!(deque.chunks_vectored(&mut slices), 2);
+    assert_eq!((&*slices[0], &*slices[1]), (&[3, 4][..], &[5][..]));
+    assert_eq!(deque.chunks_vectored(&mut slices[..1]), 1);
+
+    let mut tiny: TinyDeque<[u8; 2]> = deque.iter().copied().collect();
+    tiny.advance(1);
+    assert_eq!(tiny.get_u16(), 0x0405);
+    assert_eq!(tiny.chunks_vectored(&mut slices), 0);
+}
@@ -0,0 +1,157 @@
+// MIT/Apache2 License
+
+//! `bytes::BufMut` implementations, so codecs can use byte deques as read buffers.
+//!
+//! `BufMut` is an `unsafe` trait, so this is the one module of the crate that is allowed
+//! `unsafe` code. An `ArrayDeque` only hands out bytes that are already initialized, so only the
+//! spilled `TinyDeque` actually relies on it.
+
+#![cfg(feature = "bytes")]
+#![allow(unsafe_code)]
+
+use super::array_deque::ArrayDeque;
+#[cfg(feature = "alloc")]
+use super::tiny_deque::TinyDeque;
+#[cfg(feature = "alloc")]
+use alloc::{collections::VecDeque, vec::Vec};
+use bytes::{buf::UninitSlice, BufMut};
+#[cfg(feature = "alloc")]
+use core::mem;
+use tinyvec::Array;
+
+/// Writing to a byte `ArrayDeque` fills the free space at the back of its ring.
+///
+/// # Example
+///
+/// ```
+/// use bytes::{Buf, BufMut};
+/// use tinydeque::ArrayDeque;
+///
+/// let mut frames: ArrayDeque<[u8; 8]> = ArrayDeque::new();
+/// frames.put_slice(b"\x00\x03abc");
+/// assert_eq!(frames.get_u16(), 3);
+/// frames.advance(3);
+///
+/// // the free space wraps around the end of the array
+/// assert_eq!(frames.remaining_mut(), 8);
+/// assert_eq!(frames.chunk_mut().len(), 3);
+/// frames.put_u16(4);
+/// frames.put_slice(b"wxyz");
+/// assert_eq!(frames.get_u16(), 4);
+/// assert_eq!(&frames.copy_to_bytes(4)[..], b"wxyz");
+/// ```
+unsafe impl<A: Array<Item = u8>> BufMut for ArrayDeque<A> {
+    #[inline]
+    fn remaining_mut(&self) -> usize {
+        Self::capacity() - self.len()
+    }
+
+    #[inline]
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        // SAFETY: every slot of the array is initialized, so committing them is always sound.
+        // Committing more than `remaining_mut` bytes panics.
+        self.commit_back(cnt);
+    }
+
+    #[inline]
+    fn chunk_mut(&mut self) -> &mut UninitSlice {
+        UninitSlice::new(self.spare_capacity_mut().0)
+    }
+}
+
+/// Writing to a byte `TinyDeque` spills it onto the heap once it is full, and grows it from then
+/// on.
+///
+/// A spilled `TinyDeque` lends out the spare capacity of its `VecDeque`. To do so, its contents
+/// are moved to the start of the allocation, which costs a copy if bytes have been read from
+/// the front since the last write.
+///
+/// # Example
+///
+/// ```
+/// use bytes::{Buf, BufMut};
+/// use tinydeque::TinyDeque;
+///
+/// let mut buffer: TinyDeque<[u8; 4]> = TinyDeque::new();
+/// buffer.put_slice(b"hel");
+/// assert_eq!(buffer.chunk_mut().len(), 1);
+/// buffer.put_slice(b"lo world");
+/// assert!(matches!(buffer, TinyDeque::Heap(_)));
+///
+/// buffer.advance(6);
+/// assert!(buffer.chunk_mut().len() > 0);
+/// buffer.put_u8(b'!');
+/// assert_eq!(&buffer.copy_to_bytes(6)[..], b"world!");
+/// ```
+#[cfg(feature = "alloc")]
+unsafe impl<A: Array<Item = u8>> BufMut for TinyDeque<A> {
+    #[inline]
+    fn remaining_mut(&self) -> usize {
+        isize::MAX.unsigned_abs() - self.len()
+    }
+
+    #[inline]
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        match self {
+            Self::Stack(s) => s.commit_back(cnt),
+            Self::Heap(v) => {
+                let mut vec = Vec::from(mem::take(v));
+                assert!(
+                    cnt <= vec.capacity() - vec.len(),
+                    "<TinyDeque> Cannot advance past the spare capacity"
+                );
+                // SAFETY: `chunk_mut` left the contents at the start of the allocation, so the
+                // conversion did not move them, and the caller has initialized the first `cnt`
+                // bytes after them.
+                vec.set_len(vec.len() + cnt);
+                *v = VecDeque::from(vec);
+            }
+        }
+    }
+
+    #[inline]
+    fn chunk_mut(&mut self) -> &mut UninitSlice {
+        if matches!(self, Self::Stack(s) if s.is_full()) {
+            self.spill();
+        }
+
+        match self {
+            Self::Stack(s) => UninitSlice::new(s.spare_capacity_mut().0),
+            Self::Heap(v) => heap_chunk_mut(v),
+        }
+    }
+}
+
+/// The spare capacity of a spilled `TinyDeque`, after moving its contents to the start of the
+/// allocation.
+#[cfg(feature = "alloc")]
+#[inline]
+fn heap_chunk_mut(v: &mut VecDeque<u8>) -> &mut UninitSlice {
+    let mut vec = Vec::from(mem::take(v));
+    vec.reserve(64);
+    let spare = vec.spare_capacity_mut();
+    let (ptr, len) = (spare.as_mut_ptr().cast::<u8>(), spare.len());
+    *v = VecDeque::from(vec);
+
+    // SAFETY: converting a `Vec` into a `VecDeque` keeps its allocation, so `ptr` still points
+    // to `len` unused bytes at the end of the contents of `v`, and `v` stays mutably borrowed
+    // for as long as the slice lives.
+    unsafe { UninitSlice::from_raw_parts_mut(ptr, len) }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_buf_mut_spilled_tiny_deque() {
+    use bytes::Buf;
+
+    let mut deque: TinyDeque<[u8; 2]> = TinyDeque::new();
+    for round in 0..100_u8 {
+        deque.put_slice(&[round; 5]);
+        deque.advance(3);
+        assert_eq!(deque.len(), 2 * usize::from(round) + 2);
+    }
+    assert!(matches!(deque, TinyDeque::Heap(_)));
+
+    let expected = (0..100_u8).flat_map(|round| [round; 5]).skip(300);
+    assert!(deque.iter().copied().eq(expected));
+}
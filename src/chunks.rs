@@ -0,0 +1,232 @@
+// MIT/Apache2 License
+
+//! Chunked and windowed iteration over the two halves of a ring buffer.
+//!
+//! Every chunk or window is yielded as a [`DequeSlice`], so one that crosses the wrap point of
+//! the ring is made up of two physical slices. No elements are copied.
+
+use super::deque_slice::DequeSlice;
+use core::iter::FusedIterator;
+
+/// An iterator over `n`-element chunks of a deque, starting at the front.
+///
+/// The last chunk will be shorter than `n` if the length of the deque is not divisible by `n`.
+#[derive(Debug, Clone)]
+pub struct Chunks<'a, T> {
+    slice: DequeSlice<'a, T>,
+    start: usize,
+    end: usize,
+    size: usize,
+}
+
+impl<'a, T> Chunks<'a, T> {
+    #[inline]
+    pub(crate) fn new(slice: DequeSlice<'a, T>, size: usize) -> Self {
+        assert!(size != 0, "Chunk size must be non-zero");
+        Self {
+            slice,
+            start: 0,
+            end: slice.len(),
+            size,
+        }
+    }
+}
+
+impl<'a, T> Iterator for Chunks<'a, T> {
+    type Item = DequeSlice<'a, T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            None
+        } else {
+            let start = self.start;
+            self.start = core::cmp::min(start + self.size, self.end);
+            Some(self.slice.range(start..self.start))
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.end - self.start).div_ceil(self.size);
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Chunks<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            None
+        } else {
+            let end = self.end;
+            let remainder = (end - self.start) % self.size;
+            self.end -= if remainder == 0 { self.size } else { remainder };
+            Some(self.slice.range(self.end..end))
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for Chunks<'_, T> {}
+
+impl<T> FusedIterator for Chunks<'_, T> {}
+
+/// An iterator over `n`-element chunks of a deque, starting at the front.
+///
+/// If the length of the deque is not divisible by `n`, the last up to `n - 1` elements are
+/// not yielded, and can be retrieved from [`remainder`](ChunksExact::remainder) instead.
+#[derive(Debug, Clone)]
+pub struct ChunksExact<'a, T> {
+    slice: DequeSlice<'a, T>,
+    start: usize,
+    end: usize,
+    size: usize,
+}
+
+impl<'a, T> ChunksExact<'a, T> {
+    #[inline]
+    pub(crate) fn new(slice: DequeSlice<'a, T>, size: usize) -> Self {
+        assert!(size != 0, "Chunk size must be non-zero");
+        let len = slice.len();
+        Self {
+            slice,
+            start: 0,
+            end: len - len % size,
+            size,
+        }
+    }
+
+    /// Get the elements at the back of the deque that do not fit into a whole chunk.
+    #[inline]
+    #[must_use]
+    pub fn remainder(&self) -> DequeSlice<'a, T> {
+        let len = self.slice.len();
+        self.slice.range(len - len % self.size..)
+    }
+}
+
+impl<'a, T> Iterator for ChunksExact<'a, T> {
+    type Item = DequeSlice<'a, T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            None
+        } else {
+            let start = self.start;
+            self.start += self.size;
+            Some(self.slice.range(start..self.start))
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.end - self.start) / self.size;
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for ChunksExact<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            None
+        } else {
+            let end = self.end;
+            self.end -= self.size;
+            Some(self.slice.range(self.end..end))
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for ChunksExact<'_, T> {}
+
+impl<T> FusedIterator for ChunksExact<'_, T> {}
+
+/// An iterator over all overlapping windows of `n` elements of a deque, starting at the front.
+///
+/// If the deque is shorter than `n`, no windows are yielded.
+#[derive(Debug, Clone)]
+pub struct Windows<'a, T> {
+    slice: DequeSlice<'a, T>,
+    start: usize,
+    end: usize,
+    size: usize,
+}
+
+impl<'a, T> Windows<'a, T> {
+    #[inline]
+    pub(crate) fn new(slice: DequeSlice<'a, T>, size: usize) -> Self {
+        assert!(size != 0, "Window size must be non-zero");
+        Self {
+            slice,
+            start: 0,
+            end: (slice.len() + 1).saturating_sub(size),
+            size,
+        }
+    }
+}
+
+impl<'a, T> Iterator for Windows<'a, T> {
+    type Item = DequeSlice<'a, T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            None
+        } else {
+            let start = self.start;
+            self.start += 1;
+            Some(self.slice.range(start..start + self.size))
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Windows<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            None
+        } else {
+            self.end -= 1;
+            Some(self.slice.range(self.end..self.end + self.size))
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for Windows<'_, T> {}
+
+impl<T> FusedIterator for Windows<'_, T> {}
+
+#[test]
+fn test_chunks_across_wrap() {
+    let slice = DequeSlice::new(&[1, 2, 3], &[4, 5]);
+
+    let mut chunks = Chunks::new(slice, 2);
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(chunks.next().unwrap().as_slices(), (&[1, 2][..], &[][..]));
+    assert_eq!(chunks.next().unwrap().as_slices(), (&[3][..], &[4][..]));
+    assert_eq!(chunks.next().unwrap(), &[5][..]);
+    assert_eq!(chunks.next(), None);
+    assert_eq!(Chunks::new(slice, 2).next_back().unwrap(), &[5][..]);
+
+    let mut exact = ChunksExact::new(slice, 2);
+    assert_eq!(exact.next_back().unwrap(), &[3, 4][..]);
+    assert_eq!(exact.next().unwrap(), &[1, 2][..]);
+    assert_eq!(exact.next(), None);
+    assert_eq!(exact.remainder(), &[5][..]);
+
+    let mut windows = Windows::new(slice, 3);
+    assert_eq!(windows.len(), 3);
+    assert_eq!(windows.next().unwrap(), &[1, 2, 3][..]);
+    assert_eq!(windows.next().unwrap().as_slices(), (&[2, 3][..], &[4][..]));
+    assert_eq!(windows.next_back().unwrap(), &[3, 4, 5][..]);
+    assert_eq!(windows.next(), None);
+    assert_eq!(Windows::new(slice, 6).next(), None);
+}
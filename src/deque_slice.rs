@@ -0,0 +1,516 @@
+// MIT/Apache2 License
+
+//! Borrowed views over a logical range of a deque.
+//!
+//! The contents of a ring buffer are stored in up to two physical slices. A `DequeSlice` holds
+//! both of them, so that a logical range of the deque can be handled as a single object.
+
+use core::{
+    fmt,
+    iter::Chain,
+    ops::{Bound, Index, RangeBounds},
+    slice,
+};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Resolve a range against a sequence of length `len`.
+///
+/// # Panics
+///
+/// This function will panic if the range is out of bounds or decreasing.
+#[inline]
+pub(crate) fn resolve_range<R: RangeBounds<usize>>(range: &R, len: usize) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.checked_add(1).expect("Range start out of bounds"),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.checked_add(1).expect("Range end out of bounds"),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    assert!(start <= end, "Range starts after it ends");
+    assert!(end <= len, "Range end out of bounds");
+    (start, end)
+}
+
+/// A borrowed view over a logical range of a deque, made up of two physical slices.
+///
+/// The first slice is only empty if the whole view is empty.
+///
+/// # Example
+///
+/// ```
+/// use tinydeque::ArrayDeque;
+///
+/// let mut packet: ArrayDeque<[u8; 6]> = (0..6).collect();
+/// packet.pop_front();
+/// packet.push_back(6);
+///
+/// let body = packet.range(1..5);
+/// assert_eq!(body.len(), 4);
+/// assert_eq!(body, &[2, 3, 4, 5][..]);
+/// assert_eq!(body.as_slices(), (&[2, 3, 4, 5][..], &[][..]));
+///
+/// let (header, trailer) = packet.as_deque_slice().split_at(4);
+/// assert_eq!(header.first(), Some(&1));
+/// assert_eq!(trailer, &[5, 6][..]);
+/// assert_eq!(trailer.as_slices(), (&[5][..], &[6][..]));
+/// ```
+pub struct DequeSlice<'a, T> {
+    front: &'a [T],
+    back: &'a [T],
+}
+
+impl<T> Clone for DequeSlice<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for DequeSlice<'_, T> {}
+
+impl<T> Default for DequeSlice<'_, T> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            front: &[],
+            back: &[],
+        }
+    }
+}
+
+impl<'a, T> DequeSlice<'a, T> {
+    /// Create a view over `front` followed by `back`.
+    #[inline]
+    #[must_use]
+    pub fn new(front: &'a [T], back: &'a [T]) -> Self {
+        if front.is_empty() {
+            Self {
+                front: back,
+                back: &[],
+            }
+        } else {
+            Self { front, back }
+        }
+    }
+
+    /// Get the two physical slices that make up this view.
+    #[inline]
+    #[must_use]
+    pub fn as_slices(&self) -> (&'a [T], &'a [T]) {
+        (self.front, self.back)
+    }
+
+    /// Get the number of elements in this view.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    /// Tell whether this view is empty.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.front.is_empty()
+    }
+
+    /// Tell whether this view is stored in a single physical slice.
+    #[inline]
+    #[must_use]
+    pub fn is_contiguous(&self) -> bool {
+        self.back.is_empty()
+    }
+
+    /// Get the element at the given index.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&'a T> {
+        match self.front.get(index) {
+            Some(item) => Some(item),
+            None => self.back.get(index - self.front.len()),
+        }
+    }
+
+    /// Get the first element in this view.
+    #[inline]
+    #[must_use]
+    pub fn first(&self) -> Option<&'a T> {
+        self.front.first()
+    }
+
+    /// Get the last element in this view.
+    #[inline]
+    #[must_use]
+    pub fn last(&self) -> Option<&'a T> {
+        self.back.last().or_else(|| self.front.last())
+    }
+
+    /// Iterate over the elements in this view.
+    #[inline]
+    pub fn iter(&self) -> Chain<slice::Iter<'a, T>, slice::Iter<'a, T>> {
+        self.front.iter().chain(self.back.iter())
+    }
+
+    /// Get a view over a sub-range of this view.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the range is out of bounds.
+    #[inline]
+    #[must_use]
+    pub fn range<R: RangeBounds<usize>>(&self, range: R) -> Self {
+        let (start, end) = resolve_range(&range, self.len());
+        let split = self.front.len();
+        if start >= split {
+            Self::new(&self.back[start - split..end - split], &[])
+        } else if end <= split {
+            Self::new(&self.front[start..end], &[])
+        } else {
+            Self::new(&self.front[start..], &self.back[..end - split])
+        }
+    }
+
+    /// Divide this view into two at an index.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `mid` is greater than the length of the view.
+    #[inline]
+    #[must_use]
+    pub fn split_at(&self, mid: usize) -> (Self, Self) {
+        (self.range(..mid), self.range(mid..))
+    }
+
+    /// Copy the elements of this view into `dst`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `dst` does not have the same length as this view.
+    #[inline]
+    pub fn copy_to_slice(&self, dst: &mut [T])
+    where
+        T: Copy,
+    {
+        assert_eq!(
+            self.len(),
+            dst.len(),
+            "Destination slice length does not match the DequeSlice length"
+        );
+        let (dst_front, dst_back) = dst.split_at_mut(self.front.len());
+        dst_front.copy_from_slice(self.front);
+        dst_back.copy_from_slice(self.back);
+    }
+
+    /// Copy the elements of this view into a new `Vec`.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        let mut v = Vec::with_capacity(self.len());
+        v.extend_from_slice(self.front);
+        v.extend_from_slice(self.back);
+        v
+    }
+}
+
+impl<T> Index<usize> for DequeSlice<'_, T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: usize) -> &T {
+        self.get(index).expect("DequeSlice index out of bounds")
+    }
+}
+
+impl<'a, T> IntoIterator for DequeSlice<'a, T> {
+    type Item = &'a T;
+    type IntoIter = Chain<slice::Iter<'a, T>, slice::Iter<'a, T>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &DequeSlice<'a, T> {
+    type Item = &'a T;
+    type IntoIter = Chain<slice::Iter<'a, T>, slice::Iter<'a, T>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for DequeSlice<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for DequeSlice<'_, T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for DequeSlice<'_, T> {}
+
+impl<T: PartialEq> PartialEq<[T]> for DequeSlice<'_, T> {
+    #[inline]
+    fn eq(&self, other: &[T]) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+        let (other_front, other_back) = other.split_at(self.front.len());
+        self.front == other_front && self.back == other_back
+    }
+}
+
+impl<T: PartialEq> PartialEq<&[T]> for DequeSlice<'_, T> {
+    #[inline]
+    fn eq(&self, other: &&[T]) -> bool {
+        *self == **other
+    }
+}
+
+/// A mutable view over a logical range of a deque, made up of two physical slices.
+pub struct DequeSliceMut<'a, T> {
+    front: &'a mut [T],
+    back: &'a mut [T],
+}
+
+impl<'a, T> DequeSliceMut<'a, T> {
+    /// Create a mutable view over `front` followed by `back`.
+    #[inline]
+    pub fn new(front: &'a mut [T], back: &'a mut [T]) -> Self {
+        if front.is_empty() {
+            Self {
+                front: back,
+                back: front,
+            }
+        } else {
+            Self { front, back }
+        }
+    }
+
+    /// Reborrow this view as a read-only `DequeSlice`.
+    #[inline]
+    #[must_use]
+    pub fn as_deque_slice(&self) -> DequeSlice<'_, T> {
+        DequeSlice::new(self.front, self.back)
+    }
+
+    /// Get the two physical slices that make up this view.
+    #[inline]
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        (self.front, self.back)
+    }
+
+    /// Consume this view, returning the two physical slices that make it up.
+    #[inline]
+    #[must_use]
+    pub fn into_slices(self) -> (&'a mut [T], &'a mut [T]) {
+        (self.front, self.back)
+    }
+
+    /// Get the number of elements in this view.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    /// Tell whether this view is empty.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.front.is_empty()
+    }
+
+    /// Get the element at the given index.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.as_deque_slice().get(index)
+    }
+
+    /// Get a mutable reference to the element at the given index.
+    #[inline]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        let split = self.front.len();
+        if index < split {
+            self.front.get_mut(index)
+        } else {
+            self.back.get_mut(index - split)
+        }
+    }
+
+    /// Get the first element in this view.
+    #[inline]
+    pub fn first_mut(&mut self) -> Option<&mut T> {
+        self.front.first_mut()
+    }
+
+    /// Get the last element in this view.
+    #[inline]
+    pub fn last_mut(&mut self) -> Option<&mut T> {
+        if self.back.is_empty() {
+            self.front.last_mut()
+        } else {
+            self.back.last_mut()
+        }
+    }
+
+    /// Iterate over the elements in this view.
+    #[inline]
+    pub fn iter(&self) -> Chain<slice::Iter<'_, T>, slice::Iter<'_, T>> {
+        self.front.iter().chain(self.back.iter())
+    }
+
+    /// Iterate mutably over the elements in this view.
+    #[inline]
+    pub fn iter_mut(&mut self) -> Chain<slice::IterMut<'_, T>, slice::IterMut<'_, T>> {
+        self.front.iter_mut().chain(self.back.iter_mut())
+    }
+
+    /// Divide this view into two at an index.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `mid` is greater than the length of the view.
+    #[inline]
+    #[must_use]
+    pub fn split_at_mut(self, mid: usize) -> (Self, Self) {
+        assert!(mid <= self.len(), "DequeSliceMut split index out of bounds");
+        let split = self.front.len();
+        if mid <= split {
+            let (left, right) = self.front.split_at_mut(mid);
+            (Self::new(left, &mut []), Self::new(right, self.back))
+        } else {
+            let (left, right) = self.back.split_at_mut(mid - split);
+            (Self::new(self.front, left), Self::new(right, &mut []))
+        }
+    }
+
+    /// Copy the elements of `src` into this view.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `src` does not have the same length as this view.
+    #[inline]
+    pub fn copy_from_slice(&mut self, src: &[T])
+    where
+        T: Copy,
+    {
+        assert_eq!(
+            self.len(),
+            src.len(),
+            "Source slice length does not match the DequeSliceMut length"
+        );
+        let (src_front, src_back) = src.split_at(self.front.len());
+        self.front.copy_from_slice(src_front);
+        self.back.copy_from_slice(src_back);
+    }
+}
+
+impl<'a, T> IntoIterator for DequeSliceMut<'a, T> {
+    type Item = &'a mut T;
+    type IntoIter = Chain<slice::IterMut<'a, T>, slice::IterMut<'a, T>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.front.iter_mut().chain(self.back.iter_mut())
+    }
+}
+
+impl<'a, T> IntoIterator for &'a DequeSliceMut<'_, T> {
+    type Item = &'a T;
+    type IntoIter = Chain<slice::Iter<'a, T>, slice::Iter<'a, T>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut DequeSliceMut<'_, T> {
+    type Item = &'a mut T;
+    type IntoIter = Chain<slice::IterMut<'a, T>, slice::IterMut<'a, T>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for DequeSliceMut<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq<[T]> for DequeSliceMut<'_, T> {
+    #[inline]
+    fn eq(&self, other: &[T]) -> bool {
+        self.as_deque_slice() == *other
+    }
+}
+
+impl<T: PartialEq> PartialEq<&[T]> for DequeSliceMut<'_, T> {
+    #[inline]
+    fn eq(&self, other: &&[T]) -> bool {
+        self.as_deque_slice() == **other
+    }
+}
+
+#[test]
+fn test_deque_slice_split() {
+    let front = [1, 2, 3];
+    let back = [4, 5];
+    let view = DequeSlice::new(&front, &back);
+
+    assert_eq!(view.len(), 5);
+    assert_eq!(view.get(3), Some(&4));
+    assert_eq!(view.last(), Some(&5));
+    assert_eq!(view, &[1, 2, 3, 4, 5][..]);
+    assert_ne!(view, &[1, 2, 3, 4][..]);
+
+    let (left, right) = view.split_at(4);
+    assert_eq!(left.as_slices(), (&[1, 2, 3][..], &[4][..]));
+    assert_eq!(right.as_slices(), (&[5][..], &[][..]));
+
+    let empty = view.range(5..);
+    assert!(empty.is_empty());
+    assert_eq!(empty.last(), None);
+
+    let mut out = [0; 5];
+    view.copy_to_slice(&mut out);
+    assert_eq!(out, [1, 2, 3, 4, 5]);
+
+    let mut front = [0; 2];
+    let mut back = [0; 2];
+    let (mut left, right) = DequeSliceMut::new(&mut front, &mut back).split_at_mut(1);
+    left.copy_from_slice(&[7]);
+    assert_eq!(right.len(), 3);
+    assert_eq!(front, [7, 0]);
+}
+
+#[test]
+#[should_panic(expected = "Range end out of bounds")]
+fn test_range_inclusive_end_overflow() {
+    let view = DequeSlice::new(&[1, 2, 3], &[]);
+    let _ = view.range(..=usize::MAX);
+}
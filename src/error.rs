@@ -0,0 +1,76 @@
+// MIT/Apache2 License
+
+use core::fmt;
+
+/// The error returned when an element cannot be added to a collection because it is full.
+///
+/// The rejected element is held inside of the error, and can be retrieved using
+/// [`into_inner`](CapacityError::into_inner).
+///
+/// `CapacityError` implements `core::error::Error`, which is the same trait as
+/// `std::error::Error`, so it can be used with `?` in both `no_std` and `std` code.
+///
+/// # Example
+///
+/// ```
+/// use tinydeque::{ArrayDeque, CapacityError};
+///
+/// fn enqueue_all(queue: &mut ArrayDeque<[u8; 2]>) -> Result<(), CapacityError<u8>> {
+///     queue.try_push_back(1)?;
+///     queue.try_push_back(2)?;
+///     queue.try_push_back(3)?;
+///     Ok(())
+/// }
+///
+/// let mut queue = ArrayDeque::new();
+/// let err = enqueue_all(&mut queue).unwrap_err();
+/// assert_eq!(err.to_string(), "insufficient capacity");
+/// assert_eq!(err.into_inner(), 3);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CapacityError<T = ()> {
+    element: T,
+}
+
+impl<T> CapacityError<T> {
+    /// Create a new `CapacityError` holding the rejected element.
+    #[inline]
+    pub const fn new(element: T) -> Self {
+        Self { element }
+    }
+
+    /// Get a reference to the rejected element.
+    #[inline]
+    pub const fn element(&self) -> &T {
+        &self.element
+    }
+
+    /// Get the rejected element back.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.element
+    }
+
+    /// Discard the rejected element.
+    #[inline]
+    #[must_use]
+    pub fn simplify(self) -> CapacityError {
+        CapacityError::new(())
+    }
+}
+
+impl<T> fmt::Debug for CapacityError<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CapacityError: insufficient capacity")
+    }
+}
+
+impl<T> fmt::Display for CapacityError<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("insufficient capacity")
+    }
+}
+
+impl<T> core::error::Error for CapacityError<T> {}
@@ -0,0 +1,583 @@
+// MIT/Apache2 License
+
+//! Priority queues, implemented as binary max-heaps.
+
+use super::error::CapacityError;
+use super::heap_impls::impl_array_heap_traits;
+#[cfg(feature = "alloc")]
+use super::heap_impls::{impl_tiny_heap_traits, tiny_heap_drain};
+use core::{
+    ops::{Deref, DerefMut},
+    slice,
+};
+use tinyvec::{Array, ArrayVec, ArrayVecDrain};
+
+#[cfg(feature = "alloc")]
+use alloc::{
+    collections::binary_heap::{
+        BinaryHeap, Drain as BinaryHeapDrain, PeekMut as BinaryHeapPeekMut,
+    },
+    vec::Vec,
+};
+
+/// Move the element at `index` up until its parent is no smaller than it.
+#[inline]
+fn sift_up<T: Ord>(heap: &mut [T], mut index: usize) {
+    while index > 0 {
+        let parent = (index - 1) / 2;
+        if heap[index] <= heap[parent] {
+            break;
+        }
+        heap.swap(index, parent);
+        index = parent;
+    }
+}
+
+/// Move the element at `index` down until neither of its children is larger than it.
+#[inline]
+fn sift_down<T: Ord>(heap: &mut [T], mut index: usize) {
+    loop {
+        let left = 2 * index + 1;
+        let right = left + 1;
+        let larger = if right < heap.len() && heap[right] > heap[left] {
+            right
+        } else {
+            left
+        };
+
+        if larger >= heap.len() || heap[larger] <= heap[index] {
+            return;
+        }
+        heap.swap(index, larger);
+        index = larger;
+    }
+}
+
+/// A bounded priority queue that uses an array as backing storage.
+///
+/// Like `BinaryHeap`, this is a max-heap: `pop` and `peek` return the greatest element.
+///
+/// # Example
+///
+/// ```
+/// use tinydeque::heap::ArrayHeap;
+///
+/// let mut heap: ArrayHeap<[u32; 4]> = ArrayHeap::new();
+/// heap.push(2);
+/// heap.push(7);
+/// heap.push(4);
+///
+/// assert_eq!(heap.peek(), Some(&7));
+/// if let Some(mut top) = heap.peek_mut() {
+///     *top = 1;
+/// }
+///
+/// assert_eq!(heap.pop(), Some(4));
+/// assert_eq!(heap.into_sorted_array_vec().as_slice(), &[1, 2]);
+/// ```
+pub struct ArrayHeap<A: Array> {
+    data: ArrayVec<A>,
+}
+
+impl<A: Array> ArrayHeap<A> {
+    /// Create a new, empty `ArrayHeap`.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            data: ArrayVec::new(),
+        }
+    }
+
+    /// The maximum number of elements this `ArrayHeap` can hold.
+    #[inline]
+    #[must_use]
+    pub fn capacity() -> usize {
+        A::CAPACITY
+    }
+
+    /// Get the number of elements in this `ArrayHeap`.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Tell whether this `ArrayHeap` is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Tell whether this `ArrayHeap` is full.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() >= Self::capacity()
+    }
+
+    /// Remove every element from this `ArrayHeap`.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    /// View the elements of this `ArrayHeap` as a slice, in heap order.
+    #[inline]
+    pub fn as_slice(&self) -> &[A::Item] {
+        self.data.as_slice()
+    }
+
+    /// Iterate over the elements of this `ArrayHeap`, in no particular order.
+    #[inline]
+    pub fn iter(&self) -> slice::Iter<'_, A::Item> {
+        self.data.iter()
+    }
+
+    /// Remove every element from this `ArrayHeap`, in no particular order.
+    #[inline]
+    pub fn drain(&mut self) -> ArrayVecDrain<'_, A::Item> {
+        self.data.drain(..)
+    }
+
+    /// Get the underlying storage of this `ArrayHeap`, in heap order.
+    #[inline]
+    pub fn into_array_vec(self) -> ArrayVec<A> {
+        self.data
+    }
+}
+
+impl<A: Array> ArrayHeap<A>
+where
+    A::Item: Ord,
+{
+    /// Try to push an element onto this `ArrayHeap`.
+    ///
+    /// # Errors
+    ///
+    /// If the `ArrayHeap` is full, a `CapacityError` holding the element is returned.
+    #[inline]
+    pub fn try_push(&mut self, element: A::Item) -> Result<(), CapacityError<A::Item>> {
+        if let Some(element) = self.data.try_push(element) {
+            return Err(CapacityError::new(element));
+        }
+
+        let last = self.data.len() - 1;
+        sift_up(self.data.as_mut_slice(), last);
+        Ok(())
+    }
+
+    /// Push an element onto this `ArrayHeap`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the `ArrayHeap` is full.
+    #[inline]
+    pub fn push(&mut self, element: A::Item) {
+        if let Err(_) = self.try_push(element) {
+            panic!("<ArrayHeap> Unable to push element onto ArrayHeap, since it is full");
+        }
+    }
+
+    /// Remove and return the greatest element in this `ArrayHeap`.
+    #[inline]
+    pub fn pop(&mut self) -> Option<A::Item> {
+        if self.data.is_empty() {
+            return None;
+        }
+
+        let element = self.data.swap_remove(0);
+        sift_down(self.data.as_mut_slice(), 0);
+        Some(element)
+    }
+
+    /// Get the greatest element in this `ArrayHeap`.
+    #[inline]
+    pub fn peek(&self) -> Option<&A::Item> {
+        self.data.first()
+    }
+
+    /// Get a mutable reference to the greatest element in this `ArrayHeap`.
+    ///
+    /// The heap is reordered when the returned `PeekMut` is dropped.
+    #[inline]
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, A>> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(PeekMut { heap: self })
+        }
+    }
+
+    /// Consume this `ArrayHeap`, returning its elements in ascending order.
+    #[inline]
+    pub fn into_sorted_array_vec(self) -> ArrayVec<A> {
+        let mut data = self.data;
+        data.as_mut_slice().sort_unstable();
+        data
+    }
+}
+
+impl_array_heap_traits!(ArrayHeap);
+
+/// A mutable reference to the greatest element of an `ArrayHeap`.
+///
+/// When this is dropped, the element is moved to its proper place in the heap.
+pub struct PeekMut<'a, A: Array>
+where
+    A::Item: Ord,
+{
+    heap: &'a mut ArrayHeap<A>,
+}
+
+impl<A: Array> PeekMut<'_, A>
+where
+    A::Item: Ord,
+{
+    /// Remove the peeked element from the heap and return it.
+    #[inline]
+    #[allow(clippy::must_use_candidate)]
+    pub fn pop(this: Self) -> A::Item {
+        let element = this.heap.data.swap_remove(0);
+        // dropping the guard moves the replacement element into place
+        drop(this);
+        element
+    }
+}
+
+impl<A: Array> Deref for PeekMut<'_, A>
+where
+    A::Item: Ord,
+{
+    type Target = A::Item;
+
+    #[inline]
+    fn deref(&self) -> &A::Item {
+        &self.heap.data[0]
+    }
+}
+
+impl<A: Array> DerefMut for PeekMut<'_, A>
+where
+    A::Item: Ord,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut A::Item {
+        &mut self.heap.data[0]
+    }
+}
+
+impl<A: Array> Drop for PeekMut<'_, A>
+where
+    A::Item: Ord,
+{
+    #[inline]
+    fn drop(&mut self) {
+        sift_down(self.heap.data.as_mut_slice(), 0);
+    }
+}
+
+/// A priority queue that can overflow onto the heap if it spills the stack.
+///
+/// # Example
+///
+/// ```
+/// use tinydeque::heap::TinyHeap;
+///
+/// let mut heap: TinyHeap<[u32; 2]> = TinyHeap::new();
+/// heap.extend([3, 8, 5].iter().copied());
+///
+/// assert!(heap.is_spilled());
+/// assert_eq!(heap.pop(), Some(8));
+/// assert_eq!(heap.into_sorted_vec(), vec![3, 5]);
+/// ```
+#[cfg(feature = "alloc")]
+pub enum TinyHeap<A: Array> {
+    Stack(ArrayHeap<A>),
+    Heap(BinaryHeap<A::Item>),
+}
+
+#[cfg(feature = "alloc")]
+impl<A: Array> TinyHeap<A> {
+    /// Create a new `TinyHeap`.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::Stack(ArrayHeap::new())
+    }
+
+    /// Tell whether this `TinyHeap` has spilled onto the heap.
+    #[inline]
+    pub fn is_spilled(&self) -> bool {
+        matches!(self, Self::Heap(_))
+    }
+
+    /// Get the number of elements in this `TinyHeap`.
+    #[inline]
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Stack(s) => s.len(),
+            Self::Heap(h) => h.len(),
+        }
+    }
+
+    /// Tell whether this `TinyHeap` is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::Stack(s) => s.is_empty(),
+            Self::Heap(h) => h.is_empty(),
+        }
+    }
+
+    /// Remove every element from this `TinyHeap`.
+    #[inline]
+    pub fn clear(&mut self) {
+        match self {
+            Self::Stack(s) => s.clear(),
+            Self::Heap(h) => h.clear(),
+        }
+    }
+
+    /// View the elements of this `TinyHeap` as a slice, in heap order.
+    #[inline]
+    pub fn as_slice(&self) -> &[A::Item] {
+        match self {
+            Self::Stack(s) => s.as_slice(),
+            Self::Heap(h) => h.as_slice(),
+        }
+    }
+
+    /// Iterate over the elements of this `TinyHeap`, in no particular order.
+    #[inline]
+    pub fn iter(&self) -> slice::Iter<'_, A::Item> {
+        self.as_slice().iter()
+    }
+
+    /// Remove every element from this `TinyHeap`, in no particular order.
+    #[inline]
+    pub fn drain(&mut self) -> Drain<'_, A> {
+        match self {
+            Self::Stack(s) => Drain::Stack(s.drain()),
+            Self::Heap(h) => Drain::Heap(h.drain()),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<A: Array> TinyHeap<A>
+where
+    A::Item: Ord,
+{
+    /// Create a new `TinyHeap` with the specified capacity. If the capacity is greater than the
+    /// array capacity, it will spill onto the heap.
+    ///
+    /// This needs `Ord` only because `BinaryHeap::with_capacity` does on the oldest supported
+    /// Rust version.
+    #[inline]
+    #[must_use]
+    pub fn with_capacity(cap: usize) -> Self {
+        if cap > A::CAPACITY {
+            Self::Heap(BinaryHeap::with_capacity(cap))
+        } else {
+            Self::Stack(ArrayHeap::new())
+        }
+    }
+
+    /// Push an element onto this `TinyHeap`.
+    #[inline]
+    pub fn push(&mut self, element: A::Item) {
+        match self {
+            Self::Heap(h) => h.push(element),
+            Self::Stack(s) => {
+                if let Err(reject) = s.try_push(element) {
+                    self.spill();
+                    self.as_heap_mut().push(reject.into_inner());
+                }
+            }
+        }
+    }
+
+    /// Try to push an element onto this `TinyHeap`, without spilling onto the heap.
+    ///
+    /// # Errors
+    ///
+    /// If the `TinyHeap` is still on the stack and is full, a `CapacityError` holding the element
+    /// is returned.
+    #[inline]
+    pub fn try_push(&mut self, element: A::Item) -> Result<(), CapacityError<A::Item>> {
+        match self {
+            Self::Heap(h) => {
+                h.push(element);
+                Ok(())
+            }
+            Self::Stack(s) => s.try_push(element),
+        }
+    }
+
+    /// Remove and return the greatest element in this `TinyHeap`.
+    #[inline]
+    pub fn pop(&mut self) -> Option<A::Item> {
+        match self {
+            Self::Stack(s) => s.pop(),
+            Self::Heap(h) => h.pop(),
+        }
+    }
+
+    /// Get the greatest element in this `TinyHeap`.
+    #[inline]
+    pub fn peek(&self) -> Option<&A::Item> {
+        match self {
+            Self::Stack(s) => s.peek(),
+            Self::Heap(h) => h.peek(),
+        }
+    }
+
+    /// Get a mutable reference to the greatest element in this `TinyHeap`.
+    ///
+    /// The heap is reordered when the returned `TinyPeekMut` is dropped.
+    #[inline]
+    pub fn peek_mut(&mut self) -> Option<TinyPeekMut<'_, A>> {
+        match self {
+            Self::Stack(s) => s.peek_mut().map(TinyPeekMut::Stack),
+            Self::Heap(h) => h.peek_mut().map(TinyPeekMut::Heap),
+        }
+    }
+
+    /// Consume this `TinyHeap`, returning its elements in ascending order.
+    #[inline]
+    pub fn into_sorted_vec(self) -> Vec<A::Item> {
+        match self {
+            Self::Stack(s) => s.into_sorted_array_vec().drain_to_vec(),
+            Self::Heap(h) => h.into_sorted_vec(),
+        }
+    }
+
+    #[inline]
+    fn as_heap_mut(&mut self) -> &mut BinaryHeap<A::Item> {
+        match self {
+            Self::Heap(h) => h,
+            Self::Stack(_) => unreachable!(),
+        }
+    }
+
+    #[inline]
+    fn spill(&mut self) {
+        let stack = match self {
+            Self::Heap(_) => return,
+            Self::Stack(ref mut s) => s,
+        };
+        // the elements are already in heap order, so this does not reorder anything
+        let heap = stack.data.drain_to_vec_and_reserve(1);
+        *self = Self::Heap(BinaryHeap::from(heap));
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<A: Array> From<TinyHeap<A>> for BinaryHeap<A::Item>
+where
+    A::Item: Ord,
+{
+    #[inline]
+    fn from(heap: TinyHeap<A>) -> Self {
+        match heap {
+            TinyHeap::Stack(s) => BinaryHeap::from(s.into_array_vec().drain_to_vec()),
+            TinyHeap::Heap(h) => h,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl_tiny_heap_traits!(TinyHeap, ArrayHeap);
+
+/// A mutable reference to the greatest element of a `TinyHeap`.
+#[cfg(feature = "alloc")]
+pub enum TinyPeekMut<'a, A: Array>
+where
+    A::Item: Ord,
+{
+    Stack(PeekMut<'a, A>),
+    Heap(BinaryHeapPeekMut<'a, A::Item>),
+}
+
+#[cfg(feature = "alloc")]
+impl<A: Array> TinyPeekMut<'_, A>
+where
+    A::Item: Ord,
+{
+    /// Remove the peeked element from the heap and return it.
+    #[inline]
+    #[allow(clippy::must_use_candidate)]
+    pub fn pop(this: Self) -> A::Item {
+        match this {
+            Self::Stack(s) => PeekMut::pop(s),
+            Self::Heap(h) => BinaryHeapPeekMut::pop(h),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<A: Array> Deref for TinyPeekMut<'_, A>
+where
+    A::Item: Ord,
+{
+    type Target = A::Item;
+
+    #[inline]
+    fn deref(&self) -> &A::Item {
+        match self {
+            Self::Stack(s) => s,
+            Self::Heap(h) => h,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<A: Array> DerefMut for TinyPeekMut<'_, A>
+where
+    A::Item: Ord,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut A::Item {
+        match self {
+            Self::Stack(s) => s,
+            Self::Heap(h) => h,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+tiny_heap_drain! {
+    /// A draining iterator over the elements of a `TinyHeap`.
+    BinaryHeapDrain
+}
+
+#[test]
+fn test_heap_peek_mut_and_spill() {
+    let mut heap: ArrayHeap<[i32; 8]> = [5, -3, 9, 1, 9, 0].iter().copied().collect();
+    assert_eq!(heap.peek(), Some(&9));
+
+    // lowering the top element moves it back down when the guard is dropped
+    *heap.peek_mut().unwrap() = -10;
+    assert_eq!(heap.peek(), Some(&9));
+    assert_eq!(heap.peek_mut().map(PeekMut::pop), Some(9));
+    assert_eq!(heap.pop(), Some(5));
+    assert_eq!(
+        heap.clone().into_sorted_array_vec().as_slice(),
+        &[-10, -3, 0, 1]
+    );
+    assert_eq!(heap.drain().len(), 4);
+    assert!(heap.is_empty());
+
+    #[cfg(feature = "alloc")]
+    {
+        let mut tiny: TinyHeap<[i32; 2]> = TinyHeap::new();
+        tiny.push(4);
+        tiny.push(6);
+        assert_eq!(tiny.try_push(8).unwrap_err().into_inner(), 8);
+        tiny.push(8);
+        assert!(tiny.is_spilled());
+
+        *tiny.peek_mut().unwrap() = 0;
+        assert_eq!(tiny.peek(), Some(&6));
+        assert_eq!(tiny.into_sorted_vec(), alloc::vec![0, 4, 6]);
+    }
+}
@@ -0,0 +1,176 @@
+// MIT/Apache2 License
+
+//! Trait implementations shared by the priority queues in `heap` and `min_max_heap`.
+//!
+//! Both modules pair an array-backed heap, which keeps its elements in an `ArrayVec` named
+//! `data`, with a `Stack`/`Heap` enum that spills onto the heap. The traits that do not depend on
+//! the heap ordering are written once here, so that the two stay in sync.
+
+/// Implement `Default`, `Clone`, `Debug`, `FromIterator`, `Extend` and `IntoIterator` for
+/// references on an array-backed heap with `new`, `iter` and `push` methods.
+macro_rules! impl_array_heap_traits {
+    ($heap:ident) => {
+        impl<A: ::tinyvec::Array> Default for $heap<A> {
+            #[inline]
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<A: ::tinyvec::Array> Clone for $heap<A>
+        where
+            A::Item: Clone,
+        {
+            #[inline]
+            fn clone(&self) -> Self {
+                Self {
+                    data: self.data.iter().cloned().collect(),
+                }
+            }
+        }
+
+        $crate::heap_impls::impl_heap_common_traits!($heap);
+    };
+}
+
+/// Implement `Default`, `Clone`, `Debug`, `From` the array-backed heap, `FromIterator`, `Extend`
+/// and `IntoIterator` for references on a spilling heap enum with `new`, `iter` and `push`
+/// methods.
+#[cfg(feature = "alloc")]
+macro_rules! impl_tiny_heap_traits {
+    ($tiny:ident, $array:ident) => {
+        impl<A: ::tinyvec::Array> Default for $tiny<A> {
+            #[inline]
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<A: ::tinyvec::Array> Clone for $tiny<A>
+        where
+            A::Item: Clone,
+        {
+            #[inline]
+            fn clone(&self) -> Self {
+                match self {
+                    Self::Stack(s) => Self::Stack(s.clone()),
+                    Self::Heap(h) => Self::Heap(h.clone()),
+                }
+            }
+        }
+
+        impl<A: ::tinyvec::Array> From<$array<A>> for $tiny<A> {
+            #[inline]
+            fn from(heap: $array<A>) -> Self {
+                Self::Stack(heap)
+            }
+        }
+
+        $crate::heap_impls::impl_heap_common_traits!($tiny);
+    };
+}
+
+/// The traits that are implemented the same way for both kinds of heap.
+macro_rules! impl_heap_common_traits {
+    ($heap:ident) => {
+        impl<A: ::tinyvec::Array> ::core::fmt::Debug for $heap<A>
+        where
+            A::Item: ::core::fmt::Debug,
+        {
+            #[inline]
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.debug_list().entries(self.iter()).finish()
+            }
+        }
+
+        impl<A: ::tinyvec::Array> ::core::iter::FromIterator<A::Item> for $heap<A>
+        where
+            A::Item: Ord,
+        {
+            #[inline]
+            fn from_iter<T>(iter: T) -> Self
+            where
+                T: IntoIterator<Item = A::Item>,
+            {
+                let mut heap = Self::new();
+                heap.extend(iter);
+                heap
+            }
+        }
+
+        impl<A: ::tinyvec::Array> Extend<A::Item> for $heap<A>
+        where
+            A::Item: Ord,
+        {
+            #[inline]
+            fn extend<T>(&mut self, iter: T)
+            where
+                T: IntoIterator<Item = A::Item>,
+            {
+                iter.into_iter().for_each(|item| self.push(item));
+            }
+        }
+
+        impl<'a, A: ::tinyvec::Array> IntoIterator for &'a $heap<A> {
+            type Item = &'a A::Item;
+            type IntoIter = ::core::slice::Iter<'a, A::Item>;
+
+            #[inline]
+            fn into_iter(self) -> Self::IntoIter {
+                self.iter()
+            }
+        }
+    };
+}
+
+/// Define the `Drain` enum of a spilling heap, whose `Heap` variant wraps the given draining
+/// iterator.
+#[cfg(feature = "alloc")]
+macro_rules! tiny_heap_drain {
+    ($(#[$attr:meta])* $heap_drain:ident) => {
+        $(#[$attr])*
+        pub enum Drain<'a, A: ::tinyvec::Array> {
+            Stack(::tinyvec::ArrayVecDrain<'a, A::Item>),
+            Heap($heap_drain<'a, A::Item>),
+        }
+
+        impl<A: ::tinyvec::Array> Iterator for Drain<'_, A> {
+            type Item = A::Item;
+
+            #[inline]
+            fn next(&mut self) -> Option<A::Item> {
+                match self {
+                    Self::Stack(s) => s.next(),
+                    Self::Heap(h) => h.next(),
+                }
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                match self {
+                    Self::Stack(s) => s.size_hint(),
+                    Self::Heap(h) => h.size_hint(),
+                }
+            }
+        }
+
+        impl<A: ::tinyvec::Array> ExactSizeIterator for Drain<'_, A> {}
+
+        impl<A: ::tinyvec::Array> DoubleEndedIterator for Drain<'_, A> {
+            #[inline]
+            fn next_back(&mut self) -> Option<A::Item> {
+                match self {
+                    Self::Stack(s) => s.next_back(),
+                    Self::Heap(h) => h.next_back(),
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use impl_array_heap_traits;
+pub(crate) use impl_heap_common_traits;
+#[cfg(feature = "alloc")]
+pub(crate) use impl_tiny_heap_traits;
+#[cfg(feature = "alloc")]
+pub(crate) use tiny_heap_drain;
@@ -0,0 +1,352 @@
+// MIT/Apache2 License
+
+//! A command line history, for shells and serial consoles.
+
+use super::array_deque::{ArrayDeque, Iter};
+use core::fmt;
+use tinyvec::Array;
+
+/// A fixed-size history of entered lines, with a cursor for browsing them.
+///
+/// Entries are kept oldest first. When the history is full, pushing a new entry evicts the
+/// oldest one. Entries can be any type that can be compared, such as a `&str` or a `String`;
+/// [`search_back`] also needs them to implement `AsRef<str>`.
+///
+/// The cursor starts past the newest entry, on the line that is being typed. [`prev`] moves
+/// it towards older entries and [`next`] moves it back. Pushing an entry moves the cursor back
+/// past the newest entry.
+///
+/// [`prev`]: History::prev
+/// [`next`]: History::next
+/// [`search_back`]: History::search_back
+///
+/// # Example
+///
+/// ```
+/// use tinydeque::history::History;
+///
+/// let mut history: History<[&str; 3]> = History::new();
+/// history.push("make");
+/// history.push("make test");
+/// history.push("make test");
+/// history.push("git status");
+/// assert_eq!(history.len(), 3);
+///
+/// assert_eq!(history.prev(), Some(&"git status"));
+/// assert_eq!(history.prev(), Some(&"make test"));
+/// assert_eq!(history.next(), Some(&"git status"));
+/// assert_eq!(history.next(), None);
+///
+/// assert_eq!(history.search_back("make"), Some(&"make test"));
+/// assert_eq!(history.search_back("make"), Some(&"make"));
+/// assert_eq!(history.search_back("make"), None);
+/// ```
+pub struct History<A: Array> {
+    entries: ArrayDeque<A>,
+    // the index of the selected entry, or `len` when no entry is selected
+    cursor: usize,
+}
+
+impl<A: Array> Default for History<A> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: Array> Clone for History<A>
+where
+    A::Item: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            entries: self.entries.clone(),
+            cursor: self.cursor,
+        }
+    }
+}
+
+impl<A: Array> fmt::Debug for History<A>
+where
+    A::Item: fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("History")
+            .field("entries", &self.entries)
+            .field("cursor", &self.cursor())
+            .finish()
+    }
+}
+
+/// Histories are equal if they hold the same entries, wherever their cursors are.
+impl<A: Array> PartialEq for History<A>
+where
+    A::Item: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<A: Array> Eq for History<A> where A::Item: Eq {}
+
+impl<A: Array> History<A> {
+    /// Create a new, empty `History`.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            entries: ArrayDeque::new(),
+            cursor: 0,
+        }
+    }
+
+    /// The maximum number of entries this `History` can hold.
+    #[inline]
+    #[must_use]
+    pub fn capacity() -> usize {
+        A::CAPACITY
+    }
+
+    /// Get the number of entries in this `History`.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Tell whether this `History` is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Get the entry at `index`, where zero is the oldest entry.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&A::Item> {
+        self.entries.get(index)
+    }
+
+    /// Iterate over the entries, oldest first.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, A> {
+        self.entries.iter()
+    }
+
+    /// Remove all of the entries and reset the cursor.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.cursor = 0;
+    }
+
+    /// Add an entry as the newest one and reset the cursor.
+    ///
+    /// If the entry is equal to the newest entry, it is not added again. Otherwise, if the
+    /// history is full, the oldest entry is evicted and returned.
+    #[inline]
+    pub fn push(&mut self, entry: A::Item) -> Option<A::Item>
+    where
+        A::Item: PartialEq,
+    {
+        let evicted = if self.entries.back() == Some(&entry) {
+            None
+        } else {
+            self.entries.push_back_overwrite(entry)
+        };
+        self.reset_cursor();
+        evicted
+    }
+
+    /// Get the index of the entry under the cursor, or `None` if the cursor is past the newest
+    /// entry.
+    #[inline]
+    pub fn cursor(&self) -> Option<usize> {
+        if self.cursor < self.len() {
+            Some(self.cursor)
+        } else {
+            None
+        }
+    }
+
+    /// Get the entry under the cursor.
+    #[inline]
+    pub fn current(&self) -> Option<&A::Item> {
+        self.entries.get(self.cursor)
+    }
+
+    /// Move the cursor past the newest entry, back to the line that is being typed.
+    #[inline]
+    pub fn reset_cursor(&mut self) {
+        self.cursor = self.len();
+    }
+
+    /// Move the cursor to the next older entry and return it. The cursor stays on the oldest
+    /// entry once it gets there.
+    #[inline]
+    pub fn prev(&mut self) -> Option<&A::Item> {
+        self.cursor = self.cursor.saturating_sub(1);
+        self.current()
+    }
+
+    /// Move the cursor to the next newer entry and return it. Moving past the newest entry
+    /// returns `None`, for the line that is being typed.
+    #[inline]
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<&A::Item> {
+        if self.cursor < self.len() {
+            self.cursor += 1;
+        }
+        self.current()
+    }
+
+    /// Move the cursor to the next older entry that starts with `prefix` and return it.
+    ///
+    /// If no older entry matches, the cursor does not move and `None` is returned.
+    #[inline]
+    pub fn search_back(&mut self, prefix: &str) -> Option<&A::Item>
+    where
+        A::Item: AsRef<str>,
+    {
+        let entries = &self.entries;
+        self.cursor = (0..self.cursor).rev().find(|&i| {
+            entries
+                .get(i)
+                .is_some_and(|entry| entry.as_ref().starts_with(prefix))
+        })?;
+        self.current()
+    }
+}
+
+impl<'a, A: Array> IntoIterator for &'a History<A> {
+    type Item = &'a A::Item;
+    type IntoIter = Iter<'a, A>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a, A> {
+        self.iter()
+    }
+}
+
+/// The entries are saved oldest first, without the cursor.
+#[cfg(feature = "serde")]
+impl<A: Array> serde::Serialize for History<A>
+where
+    A::Item: serde::Serialize,
+{
+    #[inline]
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+/// The entries are loaded as if they were pushed in order, so consecutive duplicates are
+/// skipped and only the newest entries are kept if there are more than fit.
+#[cfg(feature = "serde")]
+impl<'de, A: Array> serde::Deserialize<'de> for History<A>
+where
+    A::Item: serde::Deserialize<'de> + PartialEq,
+{
+    #[inline]
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct HistoryVisitor<A>(core::marker::PhantomData<fn() -> A>);
+
+        impl<'de, A: Array> serde::de::Visitor<'de> for HistoryVisitor<A>
+        where
+            A::Item: serde::Deserialize<'de> + PartialEq,
+        {
+            type Value = History<A>;
+
+            #[inline]
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a sequence of history entries")
+            }
+
+            #[inline]
+            fn visit_seq<S: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: S,
+            ) -> Result<History<A>, S::Error> {
+                let mut history = History::new();
+                while let Some(entry) = seq.next_element()? {
+                    history.push(entry);
+                }
+                Ok(history)
+            }
+        }
+
+        deserializer.deserialize_seq(HistoryVisitor(core::marker::PhantomData))
+    }
+}
+
+#[test]
+fn test_history_cursor() {
+    let mut history: History<[&str; 3]> = History::new();
+    assert_eq!(history.prev(), None);
+    assert_eq!(history.next(), None);
+
+    assert_eq!(history.push("ls"), None);
+    assert_eq!(history.push("cd src"), None);
+    assert_eq!(history.push("ls"), None);
+    assert_eq!(history.push("ls"), None);
+    assert_eq!(history.push("cat lib.rs"), Some("ls"));
+    assert!(history
+        .iter()
+        .copied()
+        .eq(["cd src", "ls", "cat lib.rs"].iter().copied()));
+
+    // the cursor stops at the oldest entry
+    assert_eq!(history.prev(), Some(&"cat lib.rs"));
+    assert_eq!(history.prev(), Some(&"ls"));
+    assert_eq!(history.prev(), Some(&"cd src"));
+    assert_eq!(history.prev(), Some(&"cd src"));
+    assert_eq!(history.cursor(), Some(0));
+    assert_eq!(history.next(), Some(&"ls"));
+
+    // searching starts from the cursor, and a miss leaves it alone
+    assert_eq!(history.search_back("c"), Some(&"cd src"));
+    assert_eq!(history.search_back("c"), None);
+    assert_eq!(history.cursor(), Some(0));
+    history.reset_cursor();
+    assert_eq!(history.search_back("c"), Some(&"cat lib.rs"));
+
+    // pushing goes back to the line that is being typed
+    history.push("cat lib.rs");
+    assert_eq!(history.cursor(), None);
+    assert_eq!(history.current(), None);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_history_serde() {
+    use serde_test::{assert_de_tokens, assert_ser_tokens, Token};
+
+    let mut history: History<[&str; 2]> = History::new();
+    history.push("make");
+    history.push("make test");
+    assert_ser_tokens(
+        &history,
+        &[
+            Token::Seq { len: Some(2) },
+            Token::BorrowedStr("make"),
+            Token::BorrowedStr("make test"),
+            Token::SeqEnd,
+        ],
+    );
+
+    // loading more entries than fit keeps the newest ones
+    history.push("git status");
+    assert_de_tokens(
+        &history,
+        &[
+            Token::Seq { len: Some(4) },
+            Token::BorrowedStr("make"),
+            Token::BorrowedStr("make test"),
+            Token::BorrowedStr("make test"),
+            Token::BorrowedStr("git status"),
+            Token::SeqEnd,
+        ],
+    );
+}
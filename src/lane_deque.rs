@@ -0,0 +1,336 @@
+// MIT/Apache2 License
+
+use super::{
+    array_deque::{ArrayDeque, Drain, Iter},
+    error::CapacityError,
+};
+use core::{fmt, ops::RangeBounds};
+use tinyvec::Array;
+
+/// A set of `ArrayDeque`s, one per priority class ("lane").
+///
+/// Lane `0` has the highest priority. Elements can be taken out in strict priority order, in
+/// round-robin order, or using deficit round-robin, where each lane is given a share of the
+/// output proportional to its weight.
+///
+/// # Example
+///
+/// ```
+/// use tinydeque::lane_deque::LaneDeque;
+///
+/// let mut classes: LaneDeque<[&str; 4], 2> = LaneDeque::new();
+/// classes.push(1, "bulk-1");
+/// classes.push(1, "bulk-2");
+/// classes.push(0, "control");
+///
+/// assert_eq!(classes.pop_highest(), Some("control"));
+/// assert_eq!(classes.lane_len(1), 2);
+/// assert_eq!(classes.pop_round_robin(), Some("bulk-1"));
+/// ```
+pub struct LaneDeque<A: Array, const LANES: usize> {
+    lanes: [ArrayDeque<A>; LANES],
+    // the lane that `pop_round_robin` looks at first
+    round_robin: usize,
+    // the state of the deficit round-robin scheduler
+    weights: [usize; LANES],
+    deficits: [usize; LANES],
+    current: usize,
+    visiting: bool,
+}
+
+impl<A: Array, const LANES: usize> LaneDeque<A, LANES> {
+    /// Create a new `LaneDeque`, where every lane has a weight of one.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_weights([1; LANES])
+    }
+
+    /// Create a new `LaneDeque`, with the given weights for deficit round-robin.
+    ///
+    /// A weight of zero is treated as a weight of one.
+    #[inline]
+    #[must_use]
+    pub fn with_weights(weights: [usize; LANES]) -> Self {
+        Self {
+            lanes: core::array::from_fn(|_| ArrayDeque::new()),
+            round_robin: 0,
+            weights: weights.map(|weight| weight.max(1)),
+            deficits: [0; LANES],
+            current: 0,
+            visiting: false,
+        }
+    }
+
+    /// The number of lanes in this `LaneDeque`.
+    #[inline]
+    #[must_use]
+    pub fn lanes() -> usize {
+        LANES
+    }
+
+    /// The maximum number of elements a single lane can hold.
+    #[inline]
+    #[must_use]
+    pub fn lane_capacity() -> usize {
+        A::CAPACITY
+    }
+
+    /// Get the total number of elements in every lane.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.lanes.iter().map(ArrayDeque::len).sum()
+    }
+
+    /// Tell whether every lane is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.lanes.iter().all(ArrayDeque::is_empty)
+    }
+
+    /// Get the weight of a lane.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `lane` is out of bounds.
+    #[inline]
+    pub fn weight(&self, lane: usize) -> usize {
+        self.weights[lane]
+    }
+
+    /// Set the weight of a lane. A weight of zero is treated as a weight of one.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `lane` is out of bounds.
+    #[inline]
+    pub fn set_weight(&mut self, lane: usize, weight: usize) {
+        self.weights[lane] = weight.max(1);
+    }
+
+    /// Get a reference to a single lane.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `lane` is out of bounds.
+    #[inline]
+    pub fn lane(&self, lane: usize) -> &ArrayDeque<A> {
+        &self.lanes[lane]
+    }
+
+    /// Get the number of elements in a lane.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `lane` is out of bounds.
+    #[inline]
+    pub fn lane_len(&self, lane: usize) -> usize {
+        self.lanes[lane].len()
+    }
+
+    /// Iterate over the elements of a lane, from front to back.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `lane` is out of bounds.
+    #[inline]
+    pub fn iter_lane(&self, lane: usize) -> Iter<'_, A> {
+        self.lanes[lane].iter()
+    }
+
+    /// Remove a range of elements from a lane, returning them as an iterator.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `lane` is out of bounds, or under the same conditions as
+    /// [`ArrayDeque::drain`].
+    #[inline]
+    pub fn drain_lane<R: RangeBounds<usize>>(&mut self, lane: usize, range: R) -> Drain<'_, A> {
+        self.lanes[lane].drain(range)
+    }
+
+    /// Try to push an element onto the back of a lane.
+    ///
+    /// # Errors
+    ///
+    /// If the lane is full, a `CapacityError` holding the element is returned.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `lane` is out of bounds.
+    #[inline]
+    pub fn try_push(
+        &mut self,
+        lane: usize,
+        element: A::Item,
+    ) -> Result<(), CapacityError<A::Item>> {
+        self.lanes[lane].try_push_back(element)
+    }
+
+    /// Push an element onto the back of a lane.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `lane` is out of bounds, or if the lane is full.
+    #[inline]
+    pub fn push(&mut self, lane: usize, element: A::Item) {
+        self.lanes[lane].push_back(element);
+    }
+
+    /// Pop the front element of the highest-priority lane that is not empty.
+    #[inline]
+    pub fn pop_highest(&mut self) -> Option<A::Item> {
+        self.lanes.iter_mut().find_map(ArrayDeque::pop_front)
+    }
+
+    /// Pop the front element of the next lane that is not empty, cycling through the lanes.
+    #[inline]
+    pub fn pop_round_robin(&mut self) -> Option<A::Item> {
+        for offset in 0..LANES {
+            let lane = (self.round_robin + offset) % LANES;
+            if let Some(element) = self.lanes[lane].pop_front() {
+                self.round_robin = (lane + 1) % LANES;
+                return Some(element);
+            }
+        }
+        None
+    }
+
+    /// Pop an element using deficit round-robin, where every element costs one unit.
+    ///
+    /// Over time, each busy lane gets a share of the output proportional to its weight.
+    #[inline]
+    pub fn pop_weighted(&mut self) -> Option<A::Item> {
+        self.pop_weighted_by(|_| 1)
+    }
+
+    /// Pop an element using deficit round-robin, where `cost` gives the cost of each element.
+    ///
+    /// Every time a lane is visited, its deficit counter is credited with its weight. The front
+    /// element is taken out once the counter covers its cost, so each busy lane gets a share of
+    /// the total cost proportional to its weight. Lanes that become empty lose their credit.
+    #[inline]
+    pub fn pop_weighted_by<F>(&mut self, mut cost: F) -> Option<A::Item>
+    where
+        F: FnMut(&A::Item) -> usize,
+    {
+        if self.is_empty() {
+            return None;
+        }
+
+        loop {
+            let lane = self.current;
+            let Some(front) = self.lanes[lane].front() else {
+                self.deficits[lane] = 0;
+                self.next_visit();
+                continue;
+            };
+            let price = cost(front);
+
+            if !self.visiting {
+                self.visiting = true;
+                self.deficits[lane] = self.deficits[lane].saturating_add(self.weights[lane]);
+            }
+
+            if price > self.deficits[lane] {
+                self.next_visit();
+                continue;
+            }
+
+            self.deficits[lane] -= price;
+            let element = self.lanes[lane].pop_front();
+            if self.lanes[lane].is_empty() {
+                self.deficits[lane] = 0;
+                self.next_visit();
+            }
+            return element;
+        }
+    }
+
+    #[inline]
+    fn next_visit(&mut self) {
+        self.current = (self.current + 1) % LANES;
+        self.visiting = false;
+    }
+
+    /// Remove every element from every lane, and reset the schedulers.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.lanes.iter_mut().for_each(ArrayDeque::clear);
+        self.round_robin = 0;
+        self.deficits = [0; LANES];
+        self.current = 0;
+        self.visiting = false;
+    }
+}
+
+impl<A: Array, const LANES: usize> Default for LaneDeque<A, LANES> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: Array, const LANES: usize> Clone for LaneDeque<A, LANES>
+where
+    A::Item: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            lanes: self.lanes.clone(),
+            round_robin: self.round_robin,
+            weights: self.weights,
+            deficits: self.deficits,
+            current: self.current,
+            visiting: self.visiting,
+        }
+    }
+}
+
+impl<A: Array, const LANES: usize> fmt::Debug for LaneDeque<A, LANES>
+where
+    A::Item: fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.lanes.iter().map(ArrayDeque::as_deque_slice))
+            .finish()
+    }
+}
+
+#[test]
+fn test_lane_deque_weighted() {
+    let mut lanes: LaneDeque<[u32; 8], 3> = LaneDeque::with_weights([3, 1, 0]);
+    for i in 0..6 {
+        lanes.push(0, i);
+        lanes.push(1, 10 + i);
+    }
+    lanes.push(2, 20);
+
+    // three from the heavy lane for every one from the light lane, and a zero weight
+    // counts as one
+    let mut order = [0; 8];
+    for slot in &mut order {
+        *slot = lanes.pop_weighted().unwrap();
+    }
+    assert_eq!(order, [0, 1, 2, 10, 20, 3, 4, 5]);
+    assert_eq!(lanes.lane_len(0), 0);
+
+    // costs larger than the weight are paid off over several rounds
+    lanes.push(0, 100);
+    assert_eq!(lanes.pop_weighted_by(|&x| x as usize), Some(11));
+    assert_eq!(
+        lanes
+            .drain_lane(1, ..2)
+            .collect::<ArrayDeque<[u32; 4]>>()
+            .len(),
+        2
+    );
+    assert!(lanes.iter_lane(1).eq([14, 15].iter()));
+    assert_eq!(lanes.pop_round_robin(), Some(100));
+    assert_eq!(lanes.pop_round_robin(), Some(14));
+    assert_eq!(lanes.pop_highest(), Some(15));
+    assert_eq!(lanes.pop_weighted(), None);
+}
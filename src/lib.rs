@@ -5,15 +5,35 @@
 #![allow(clippy::cast_sign_loss)]
 #![allow(clippy::redundant_pattern_matching)]
 #![allow(clippy::cast_possible_wrap)]
-#![forbid(unsafe_code)]
+#![deny(unsafe_code)]
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 pub mod array_deque;
+mod buf;
+mod buf_mut;
+pub mod chunks;
+pub mod deque_slice;
+mod error;
+mod heap_impls;
+pub mod heap;
+pub mod history;
+pub mod lane_deque;
+pub mod lru;
+pub mod min_max_heap;
+#[cfg(feature = "std")]
+pub mod steal;
+pub mod str_deque;
+pub mod timed_deque;
 #[cfg(feature = "alloc")]
 pub mod tiny_deque;
+pub mod window;
 
 pub use array_deque::ArrayDeque;
+pub use deque_slice::{DequeSlice, DequeSliceMut};
+pub use error::CapacityError;
 #[cfg(feature = "alloc")]
 pub use tiny_deque::TinyDeque;
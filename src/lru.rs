@@ -0,0 +1,284 @@
+// MIT/Apache2 License
+
+use super::array_deque::{ArrayDeque, Iter as ArrayDequeIter};
+use core::{
+    borrow::Borrow,
+    fmt,
+    iter::{FusedIterator, Rev},
+    marker::PhantomData,
+};
+use tinyvec::Array;
+
+/// A fixed-size least-recently-used cache, backed by an `ArrayDeque`.
+///
+/// Entries are kept in recency order, and keys are found using a linear scan, so no hashing or
+/// ordering is required of the keys. This is intended for the small sizes that arrays support.
+///
+/// The storage is always an `ArrayDeque`, so the capacity is fixed and the cache never spills
+/// onto the heap like a `TinyDeque` does. Putting a new key into a full cache evicts the least
+/// recently used entry instead.
+///
+/// # Example
+///
+/// ```
+/// use tinydeque::lru::LruCache;
+///
+/// let mut dns: LruCache<&str, [u8; 4], [(&str, [u8; 4]); 2]> = LruCache::new();
+/// dns.put("example.com", [93, 184, 216, 34]);
+/// dns.put("localhost", [127, 0, 0, 1]);
+///
+/// // looking up example.com makes localhost the least recently used entry
+/// assert_eq!(dns.get(&"example.com"), Some(&[93, 184, 216, 34]));
+/// assert_eq!(dns.put("router", [192, 168, 0, 1]), Some(("localhost", [127, 0, 0, 1])));
+///
+/// assert_eq!(dns.peek(&"localhost"), None);
+/// assert!(dns.iter().map(|(k, _)| *k).eq(["router", "example.com"].iter().copied()));
+/// ```
+pub struct LruCache<K, V, A: Array<Item = (K, V)>> {
+    // the back of the deque holds the most recently used entry
+    entries: ArrayDeque<A>,
+    _marker: PhantomData<fn() -> (K, V)>,
+}
+
+impl<K, V, A: Array<Item = (K, V)>> Default for LruCache<K, V, A> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Clone, V: Clone, A: Array<Item = (K, V)>> Clone for LruCache<K, V, A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            entries: self.entries.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, A: Array<Item = (K, V)>> fmt::Debug for LruCache<K, V, A> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V, A: Array<Item = (K, V)>> LruCache<K, V, A> {
+    /// Create a new, empty `LruCache`.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            entries: ArrayDeque::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// The maximum number of entries this `LruCache` can hold.
+    #[inline]
+    #[must_use]
+    pub fn capacity() -> usize {
+        A::CAPACITY
+    }
+
+    /// Get the number of entries in this `LruCache`.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Tell whether this `LruCache` is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Tell whether this `LruCache` is full, so the next new entry will evict the least
+    /// recently used one.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.entries.is_full()
+    }
+
+    #[inline]
+    fn position<Q: ?Sized + PartialEq>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+    {
+        // the most recently used entries are the most likely to be looked up
+        self.entries.iter().rposition(|(k, _)| k.borrow() == key)
+    }
+
+    /// Move the entry at `index` to the most recently used position.
+    #[inline]
+    fn touch(&mut self, index: usize) {
+        if let Some(entry) = self.entries.remove(index) {
+            self.entries.push_back(entry);
+        }
+    }
+
+    /// Tell whether this `LruCache` holds an entry for the given key, without marking it as
+    /// used.
+    #[inline]
+    pub fn contains<Q: ?Sized + PartialEq>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+    {
+        self.position(key).is_some()
+    }
+
+    /// Get the value for the given key, and mark it as the most recently used entry.
+    #[inline]
+    pub fn get<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+    {
+        self.get_mut(key).map(|v| &*v)
+    }
+
+    /// Get a mutable reference to the value for the given key, and mark it as the most recently
+    /// used entry.
+    #[inline]
+    pub fn get_mut<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+    {
+        let index = self.position(key)?;
+        self.touch(index);
+        self.entries.back_mut().map(|(_, v)| v)
+    }
+
+    /// Get the value for the given key, without marking it as used.
+    #[inline]
+    pub fn peek<Q: ?Sized + PartialEq>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+    {
+        let index = self.position(key)?;
+        self.entries.get(index).map(|(_, v)| v)
+    }
+
+    /// Insert an entry as the most recently used one.
+    ///
+    /// If the key was already present, its old entry is replaced and returned. Otherwise, if the
+    /// cache was full, the least recently used entry is evicted and returned.
+    #[inline]
+    pub fn put(&mut self, key: K, value: V) -> Option<(K, V)>
+    where
+        K: PartialEq,
+    {
+        let displaced = match self.position(&key) {
+            Some(index) => self.entries.remove(index),
+            None if self.is_full() => self.entries.pop_front(),
+            None => None,
+        };
+
+        if Self::capacity() == 0 {
+            return Some((key, value));
+        }
+
+        self.entries.push_back((key, value));
+        displaced
+    }
+
+    /// Remove the entry for the given key, returning its value.
+    #[inline]
+    pub fn remove<Q: ?Sized + PartialEq>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+    {
+        let index = self.position(key)?;
+        self.entries.remove(index).map(|(_, v)| v)
+    }
+
+    /// Remove and return the least recently used entry.
+    #[inline]
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        self.entries.pop_front()
+    }
+
+    /// Get the least recently used entry, without marking it as used.
+    #[inline]
+    pub fn peek_lru(&self) -> Option<(&K, &V)> {
+        self.entries.front().map(|(k, v)| (k, v))
+    }
+
+    /// Remove every entry from this `LruCache`.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Iterate over the entries in this `LruCache`, from most to least recently used.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, K, V, A> {
+        Iter {
+            inner: self.entries.iter().rev(),
+        }
+    }
+}
+
+impl<'a, K, V, A: Array<Item = (K, V)>> IntoIterator for &'a LruCache<K, V, A> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V, A>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the entries of an `LruCache`, from most to least recently used.
+pub struct Iter<'a, K: 'a, V: 'a, A: Array<Item = (K, V)> + 'a> {
+    inner: Rev<ArrayDequeIter<'a, A>>,
+}
+
+impl<'a, K, V, A: Array<Item = (K, V)>> Iterator for Iter<'a, K, V, A> {
+    type Item = (&'a K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(k, v)| (k, v))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V, A: Array<Item = (K, V)>> DoubleEndedIterator for Iter<'_, K, V, A> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(k, v)| (k, v))
+    }
+}
+
+impl<K, V, A: Array<Item = (K, V)>> ExactSizeIterator for Iter<'_, K, V, A> {}
+
+impl<K, V, A: Array<Item = (K, V)>> FusedIterator for Iter<'_, K, V, A> {}
+
+#[test]
+fn test_lru_eviction_order() {
+    let mut cache: LruCache<u32, char, [(u32, char); 3]> = LruCache::new();
+    assert_eq!(cache.put(1, 'a'), None);
+    assert_eq!(cache.put(2, 'b'), None);
+    assert_eq!(cache.put(3, 'c'), None);
+
+    // replacing an entry hands back the old one and refreshes it
+    assert_eq!(cache.put(1, 'A'), Some((1, 'a')));
+    assert_eq!(cache.peek_lru(), Some((&2, &'b')));
+
+    // peeking does not refresh, but getting does
+    assert_eq!(cache.peek(&2), Some(&'b'));
+    assert_eq!(cache.get(&2), Some(&'b'));
+    assert_eq!(cache.put(4, 'd'), Some((3, 'c')));
+
+    assert_eq!(cache.remove(&1), Some('A'));
+    assert_eq!(cache.pop_lru(), Some((2, 'b')));
+    assert!(cache.iter().eq([(&4, &'d')].iter().copied()));
+
+    let mut empty: LruCache<u32, char, [(u32, char); 0]> = LruCache::new();
+    assert_eq!(empty.put(1, 'a'), Some((1, 'a')));
+}
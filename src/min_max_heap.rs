@@ -0,0 +1,548 @@
+// MIT/Apache2 License
+
+//! Double-ended priority queues, implemented as min-max heaps.
+//!
+//! A min-max heap is a binary heap whose even levels are ordered like a min-heap and whose odd
+//! levels are ordered like a max-heap, so both the smallest and the largest element can be found
+//! in constant time and removed in logarithmic time.
+
+use super::error::CapacityError;
+use super::heap_impls::impl_array_heap_traits;
+#[cfg(feature = "alloc")]
+use super::heap_impls::{impl_tiny_heap_traits, tiny_heap_drain};
+use core::slice;
+use tinyvec::{Array, ArrayVec, ArrayVecDrain};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::{Drain as VecDrain, Vec};
+
+/// Tell whether the element at `index` lives on a min level.
+#[inline]
+fn is_min_level(index: usize) -> bool {
+    let level = usize::BITS - 1 - (index + 1).leading_zeros();
+    level & 1 == 0
+}
+
+#[inline]
+fn parent(index: usize) -> usize {
+    (index - 1) / 2
+}
+
+/// Restore the heap property after an element has been placed at the end of `heap`.
+fn push_up<T: Ord>(heap: &mut [T], index: usize) {
+    if index == 0 {
+        return;
+    }
+
+    let p = parent(index);
+    if is_min_level(index) {
+        if heap[index] > heap[p] {
+            heap.swap(index, p);
+            push_up_by(heap, p, |a, b| a > b);
+        } else {
+            push_up_by(heap, index, |a, b| a < b);
+        }
+    } else if heap[index] < heap[p] {
+        heap.swap(index, p);
+        push_up_by(heap, p, |a, b| a < b);
+    } else {
+        push_up_by(heap, index, |a, b| a > b);
+    }
+}
+
+/// Move an element up through its grandparents while it is `better` than them.
+fn push_up_by<T, F: Fn(&T, &T) -> bool>(heap: &mut [T], mut index: usize, better: F) {
+    while index >= 3 {
+        let grandparent = parent(parent(index));
+        if !better(&heap[index], &heap[grandparent]) {
+            break;
+        }
+        heap.swap(index, grandparent);
+        index = grandparent;
+    }
+}
+
+/// Restore the heap property after the element at `index` has been replaced.
+fn trickle_down<T: Ord>(heap: &mut [T], index: usize) {
+    if is_min_level(index) {
+        trickle_down_by(heap, index, |a, b| a < b);
+    } else {
+        trickle_down_by(heap, index, |a, b| a > b);
+    }
+}
+
+/// Move an element down through its children and grandchildren while they are `better` than it.
+fn trickle_down_by<T, F: Fn(&T, &T) -> bool>(heap: &mut [T], mut index: usize, better: F) {
+    loop {
+        let first_child = 2 * index + 1;
+        let first_grandchild = 4 * index + 3;
+        let best = (first_child..first_child + 2)
+            .chain(first_grandchild..first_grandchild + 4)
+            .take_while(|&i| i < heap.len())
+            .fold(None, |best: Option<usize>, i| match best {
+                Some(b) if !better(&heap[i], &heap[b]) => Some(b),
+                _ => Some(i),
+            });
+
+        let best = match best {
+            Some(best) if better(&heap[best], &heap[index]) => best,
+            _ => return,
+        };
+
+        heap.swap(best, index);
+        if best < first_grandchild {
+            // a child is on the other kind of level, so it has no descendants to disturb
+            return;
+        }
+
+        let p = parent(best);
+        if better(&heap[p], &heap[best]) {
+            heap.swap(p, best);
+        }
+        index = best;
+    }
+}
+
+/// Get the index of the largest element in a heap, which is zero if the heap is empty.
+#[inline]
+fn max_index<T: Ord>(heap: &[T]) -> usize {
+    match heap.len() {
+        0 | 1 => 0,
+        2 => 1,
+        _ if heap[1] >= heap[2] => 1,
+        _ => 2,
+    }
+}
+
+/// Restore the heap property after the element at `index` has been replaced by the last one.
+#[inline]
+fn repair_removed<T: Ord>(heap: &mut [T], index: usize) {
+    if index >= heap.len() {
+        return;
+    }
+
+    // the replacement may belong on the other side of its parent
+    if index > 0 {
+        let p = parent(index);
+        let misplaced = if is_min_level(index) {
+            heap[index] > heap[p]
+        } else {
+            heap[index] < heap[p]
+        };
+        if misplaced {
+            heap.swap(index, p);
+        }
+    }
+
+    trickle_down(heap, index);
+}
+
+/// A bounded double-ended priority queue that uses an array as backing storage.
+///
+/// # Example
+///
+/// ```
+/// use tinydeque::min_max_heap::ArrayMinMaxHeap;
+///
+/// let mut deadlines: ArrayMinMaxHeap<[u32; 4]> = ArrayMinMaxHeap::new();
+/// deadlines.push(30);
+/// deadlines.push(10);
+/// deadlines.push(20);
+///
+/// assert_eq!(deadlines.peek_min(), Some(&10));
+/// assert_eq!(deadlines.peek_max(), Some(&30));
+///
+/// assert_eq!(deadlines.pop_max(), Some(30));
+/// assert_eq!(deadlines.pop_min(), Some(10));
+/// assert_eq!(deadlines.pop_min(), Some(20));
+/// assert_eq!(deadlines.pop_min(), None);
+/// ```
+pub struct ArrayMinMaxHeap<A: Array> {
+    data: ArrayVec<A>,
+}
+
+impl<A: Array> ArrayMinMaxHeap<A> {
+    /// Create a new, empty `ArrayMinMaxHeap`.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            data: ArrayVec::new(),
+        }
+    }
+
+    /// The maximum number of elements this `ArrayMinMaxHeap` can hold.
+    #[inline]
+    #[must_use]
+    pub fn capacity() -> usize {
+        A::CAPACITY
+    }
+
+    /// Get the number of elements in this `ArrayMinMaxHeap`.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Tell whether this `ArrayMinMaxHeap` is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Tell whether this `ArrayMinMaxHeap` is full.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() >= Self::capacity()
+    }
+
+    /// Remove every element from this `ArrayMinMaxHeap`.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    /// View the elements of this `ArrayMinMaxHeap` as a slice, in heap order.
+    #[inline]
+    pub fn as_slice(&self) -> &[A::Item] {
+        self.data.as_slice()
+    }
+
+    /// Iterate over the elements of this `ArrayMinMaxHeap`, in no particular order.
+    #[inline]
+    pub fn iter(&self) -> slice::Iter<'_, A::Item> {
+        self.data.iter()
+    }
+
+    /// Remove every element from this `ArrayMinMaxHeap`, in no particular order.
+    #[inline]
+    pub fn drain(&mut self) -> ArrayVecDrain<'_, A::Item> {
+        self.data.drain(..)
+    }
+
+    /// Get the underlying storage of this `ArrayMinMaxHeap`, in heap order.
+    #[inline]
+    pub fn into_array_vec(self) -> ArrayVec<A> {
+        self.data
+    }
+}
+
+impl<A: Array> ArrayMinMaxHeap<A>
+where
+    A::Item: Ord,
+{
+    /// Try to push an element onto this `ArrayMinMaxHeap`.
+    ///
+    /// # Errors
+    ///
+    /// If the `ArrayMinMaxHeap` is full, a `CapacityError` holding the element is returned.
+    #[inline]
+    pub fn try_push(&mut self, element: A::Item) -> Result<(), CapacityError<A::Item>> {
+        if let Some(element) = self.data.try_push(element) {
+            return Err(CapacityError::new(element));
+        }
+
+        let last = self.data.len() - 1;
+        push_up(self.data.as_mut_slice(), last);
+        Ok(())
+    }
+
+    /// Push an element onto this `ArrayMinMaxHeap`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the `ArrayMinMaxHeap` is full.
+    #[inline]
+    pub fn push(&mut self, element: A::Item) {
+        if let Err(_) = self.try_push(element) {
+            panic!(
+                "<ArrayMinMaxHeap> Unable to push element onto ArrayMinMaxHeap, since it is full"
+            );
+        }
+    }
+
+    /// Get the smallest element in this `ArrayMinMaxHeap`.
+    #[inline]
+    pub fn peek_min(&self) -> Option<&A::Item> {
+        self.data.first()
+    }
+
+    /// Get the largest element in this `ArrayMinMaxHeap`.
+    #[inline]
+    pub fn peek_max(&self) -> Option<&A::Item> {
+        self.data.get(max_index(&self.data))
+    }
+
+    /// Remove and return the smallest element in this `ArrayMinMaxHeap`.
+    #[inline]
+    pub fn pop_min(&mut self) -> Option<A::Item> {
+        self.remove_at(0)
+    }
+
+    /// Remove and return the largest element in this `ArrayMinMaxHeap`.
+    #[inline]
+    pub fn pop_max(&mut self) -> Option<A::Item> {
+        let index = max_index(&self.data);
+        self.remove_at(index)
+    }
+
+    #[inline]
+    fn remove_at(&mut self, index: usize) -> Option<A::Item> {
+        if index >= self.data.len() {
+            return None;
+        }
+
+        let element = self.data.swap_remove(index);
+        repair_removed(self.data.as_mut_slice(), index);
+        Some(element)
+    }
+}
+
+impl_array_heap_traits!(ArrayMinMaxHeap);
+
+/// A double-ended priority queue that can overflow onto the heap if it spills the stack.
+///
+/// # Example
+///
+/// ```
+/// use tinydeque::min_max_heap::TinyMinMaxHeap;
+///
+/// let mut jobs: TinyMinMaxHeap<[u32; 2]> = TinyMinMaxHeap::new();
+/// jobs.push(5);
+/// jobs.push(1);
+/// jobs.push(9);
+///
+/// assert!(jobs.is_spilled());
+/// assert_eq!(jobs.pop_min(), Some(1));
+/// assert_eq!(jobs.pop_max(), Some(9));
+/// ```
+#[cfg(feature = "alloc")]
+pub enum TinyMinMaxHeap<A: Array> {
+    Stack(ArrayMinMaxHeap<A>),
+    Heap(Vec<A::Item>),
+}
+
+#[cfg(feature = "alloc")]
+impl<A: Array> TinyMinMaxHeap<A> {
+    /// Create a new `TinyMinMaxHeap`.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::Stack(ArrayMinMaxHeap::new())
+    }
+
+    /// Create a new `TinyMinMaxHeap` with the specified capacity. If the capacity is greater
+    /// than the array capacity, it will spill onto the heap.
+    #[inline]
+    #[must_use]
+    pub fn with_capacity(cap: usize) -> Self {
+        if cap > A::CAPACITY {
+            Self::Heap(Vec::with_capacity(cap))
+        } else {
+            Self::Stack(ArrayMinMaxHeap::new())
+        }
+    }
+
+    /// Tell whether this `TinyMinMaxHeap` has spilled onto the heap.
+    #[inline]
+    pub fn is_spilled(&self) -> bool {
+        matches!(self, Self::Heap(_))
+    }
+
+    /// Get the number of elements in this `TinyMinMaxHeap`.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    /// Tell whether this `TinyMinMaxHeap` is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.as_slice().is_empty()
+    }
+
+    /// Remove every element from this `TinyMinMaxHeap`.
+    #[inline]
+    pub fn clear(&mut self) {
+        match self {
+            Self::Stack(s) => s.clear(),
+            Self::Heap(v) => v.clear(),
+        }
+    }
+
+    /// View the elements of this `TinyMinMaxHeap` as a slice, in heap order.
+    #[inline]
+    pub fn as_slice(&self) -> &[A::Item] {
+        match self {
+            Self::Stack(s) => s.as_slice(),
+            Self::Heap(v) => v,
+        }
+    }
+
+    /// Iterate over the elements of this `TinyMinMaxHeap`, in no particular order.
+    #[inline]
+    pub fn iter(&self) -> slice::Iter<'_, A::Item> {
+        self.as_slice().iter()
+    }
+
+    /// Remove every element from this `TinyMinMaxHeap`, in no particular order.
+    #[inline]
+    pub fn drain(&mut self) -> Drain<'_, A> {
+        match self {
+            Self::Stack(s) => Drain::Stack(s.drain()),
+            Self::Heap(v) => Drain::Heap(v.drain(..)),
+        }
+    }
+
+    #[inline]
+    fn spill(&mut self) {
+        let stack = match self {
+            Self::Heap(_) => return,
+            Self::Stack(ref mut s) => s,
+        };
+        // the array and the vector use the same layout, so the heap order carries over
+        let mut heap = Vec::with_capacity(stack.len() + 1);
+        heap.extend(stack.data.drain(..));
+        *self = Self::Heap(heap);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<A: Array> TinyMinMaxHeap<A>
+where
+    A::Item: Ord,
+{
+    /// Push an element onto this `TinyMinMaxHeap`.
+    #[inline]
+    pub fn push(&mut self, element: A::Item) {
+        let element = match self {
+            Self::Stack(s) => match s.try_push(element) {
+                Ok(()) => return,
+                Err(reject) => reject.into_inner(),
+            },
+            Self::Heap(_) => element,
+        };
+
+        self.spill();
+        if let Self::Heap(v) = self {
+            v.push(element);
+            let last = v.len() - 1;
+            push_up(v, last);
+        }
+    }
+
+    /// Try to push an element onto this `TinyMinMaxHeap`, without spilling onto the heap.
+    ///
+    /// # Errors
+    ///
+    /// If the `TinyMinMaxHeap` is still on the stack and is full, a `CapacityError` holding the
+    /// element is returned.
+    #[inline]
+    pub fn try_push(&mut self, element: A::Item) -> Result<(), CapacityError<A::Item>> {
+        match self {
+            Self::Heap(v) => {
+                v.push(element);
+                let last = v.len() - 1;
+                push_up(v, last);
+                Ok(())
+            }
+            Self::Stack(s) => s.try_push(element),
+        }
+    }
+
+    /// Get the smallest element in this `TinyMinMaxHeap`.
+    #[inline]
+    pub fn peek_min(&self) -> Option<&A::Item> {
+        self.as_slice().first()
+    }
+
+    /// Get the largest element in this `TinyMinMaxHeap`.
+    #[inline]
+    pub fn peek_max(&self) -> Option<&A::Item> {
+        let heap = self.as_slice();
+        heap.get(max_index(heap))
+    }
+
+    /// Remove and return the smallest element in this `TinyMinMaxHeap`.
+    #[inline]
+    pub fn pop_min(&mut self) -> Option<A::Item> {
+        match self {
+            Self::Stack(s) => s.pop_min(),
+            Self::Heap(v) => Self::remove_from_vec(v, 0),
+        }
+    }
+
+    /// Remove and return the largest element in this `TinyMinMaxHeap`.
+    #[inline]
+    pub fn pop_max(&mut self) -> Option<A::Item> {
+        match self {
+            Self::Stack(s) => s.pop_max(),
+            Self::Heap(v) => {
+                let index = max_index(v);
+                Self::remove_from_vec(v, index)
+            }
+        }
+    }
+
+    #[inline]
+    fn remove_from_vec(heap: &mut Vec<A::Item>, index: usize) -> Option<A::Item> {
+        if index >= heap.len() {
+            return None;
+        }
+
+        let element = heap.swap_remove(index);
+        repair_removed(heap, index);
+        Some(element)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl_tiny_heap_traits!(TinyMinMaxHeap, ArrayMinMaxHeap);
+
+#[cfg(feature = "alloc")]
+tiny_heap_drain! {
+    /// A draining iterator over the elements of a `TinyMinMaxHeap`.
+    VecDrain
+}
+
+#[test]
+fn test_min_max_heap_order() {
+    // a small linear congruential generator, so the test does not need any dependencies
+    let mut seed = 0x2545_f491_u32;
+    let mut values = [0_u32; 48];
+    for value in &mut values {
+        seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+        *value = (seed >> 16) % 100;
+    }
+
+    let mut heap: ArrayMinMaxHeap<[u32; 48]> = values.iter().copied().collect();
+    assert!(heap.is_full());
+    assert_eq!(heap.try_push(0).unwrap_err().into_inner(), 0);
+
+    let mut sorted = values;
+    sorted.sort_unstable();
+    let (mut lo, mut hi) = (0, sorted.len());
+    while lo < hi {
+        if (lo + hi) % 3 == 0 {
+            assert_eq!(heap.pop_max(), Some(sorted[hi - 1]));
+            hi -= 1;
+        } else {
+            assert_eq!(heap.pop_min(), Some(sorted[lo]));
+            lo += 1;
+        }
+        assert_eq!(heap.peek_min(), sorted[lo..hi].first());
+        assert_eq!(heap.peek_max(), sorted[lo..hi].last());
+    }
+    assert!(heap.is_empty());
+
+    #[cfg(feature = "alloc")]
+    {
+        let mut tiny: TinyMinMaxHeap<[u32; 4]> = values.iter().copied().collect();
+        assert!(tiny.is_spilled());
+        assert_eq!(tiny.pop_max(), sorted.last().copied());
+        assert_eq!(tiny.pop_min(), sorted.first().copied());
+        assert_eq!(tiny.len(), values.len() - 2);
+        assert_eq!(tiny.drain().len(), values.len() - 2);
+        assert!(tiny.is_empty());
+    }
+}
@@ -0,0 +1,320 @@
+// MIT/Apache2 License
+
+//! Bounded work-stealing deques, for job systems that keep one queue per worker thread.
+//!
+//! The owning [`Worker`] pushes and pops jobs at the back of its deque, so it sees them in LIFO
+//! order, while any number of [`Stealer`]s take jobs from the front. These are the semantics of
+//! the Chase-Lev deque. A lock-free Chase-Lev deque cannot be written without `unsafe` code,
+//! which this crate forbids, so each deque is protected by a `std::sync::Mutex` instead.
+//! [`Stealer::steal`] never waits on that mutex: if it is held, it returns [`Steal::Retry`], just
+//! as a thief that loses a race does in a lock-free implementation.
+
+#![cfg(feature = "std")]
+
+use super::{array_deque::ArrayDeque, error::CapacityError};
+use core::{cell::Cell, fmt, marker::PhantomData};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, TryLockError};
+use tinyvec::Array;
+
+/// The result of a steal attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Steal<T> {
+    /// The deque was empty.
+    Empty,
+    /// An element was stolen.
+    Success(T),
+    /// The deque was busy, and the steal should be tried again.
+    Retry,
+}
+
+impl<T> Steal<T> {
+    /// Tell whether the deque was empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        matches!(self, Self::Empty)
+    }
+
+    /// Tell whether an element was stolen.
+    #[inline]
+    pub fn is_success(&self) -> bool {
+        matches!(self, Self::Success(_))
+    }
+
+    /// Tell whether the steal should be tried again.
+    #[inline]
+    pub fn is_retry(&self) -> bool {
+        matches!(self, Self::Retry)
+    }
+
+    /// Get the stolen element, if there was one.
+    #[inline]
+    pub fn success(self) -> Option<T> {
+        match self {
+            Self::Success(element) => Some(element),
+            Self::Empty | Self::Retry => None,
+        }
+    }
+}
+
+/// Lock a deque, ignoring poisoning.
+///
+/// No operation on an `ArrayDeque` can be interrupted in a way that breaks its invariants, so a
+/// panic on another thread does not make the deque unusable.
+#[inline]
+fn lock<A: Array>(deque: &Mutex<ArrayDeque<A>>) -> MutexGuard<'_, ArrayDeque<A>> {
+    deque.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Try to lock a deque without waiting, ignoring poisoning.
+#[inline]
+fn try_lock<A: Array>(deque: &Mutex<ArrayDeque<A>>) -> Option<MutexGuard<'_, ArrayDeque<A>>> {
+    match deque.try_lock() {
+        Ok(guard) => Some(guard),
+        Err(TryLockError::Poisoned(poisoned)) => Some(poisoned.into_inner()),
+        Err(TryLockError::WouldBlock) => None,
+    }
+}
+
+/// The owner's end of a bounded work-stealing deque.
+///
+/// A `Worker` can be sent to another thread, but cannot be shared between threads; use
+/// [`stealer`](Worker::stealer) to give other threads access to the deque.
+///
+/// # Example
+///
+/// ```
+/// use std::thread;
+/// use tinydeque::steal::Worker;
+///
+/// let worker: Worker<[u32; 16]> = Worker::new();
+/// for job in 0..16 {
+///     worker.push(job).unwrap();
+/// }
+///
+/// let stealer = worker.stealer();
+/// let thief = thread::spawn(move || {
+///     let mut stolen = 0;
+///     while let Some(job) = stealer.steal_blocking() {
+///         stolen += job;
+///     }
+///     stolen
+/// });
+///
+/// let mut done = 0;
+/// while let Some(job) = worker.pop() {
+///     done += job;
+/// }
+/// assert_eq!(done + thief.join().unwrap(), (0..16).sum());
+/// ```
+pub struct Worker<A: Array> {
+    deque: Arc<Mutex<ArrayDeque<A>>>,
+    // only the owner may push and pop, so this must not be shared
+    _not_sync: PhantomData<Cell<()>>,
+}
+
+impl<A: Array> Worker<A> {
+    /// Create a new, empty work-stealing deque.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            deque: Arc::new(Mutex::new(ArrayDeque::new())),
+            _not_sync: PhantomData,
+        }
+    }
+
+    /// Create a new `Stealer` for this deque.
+    #[inline]
+    #[must_use]
+    pub fn stealer(&self) -> Stealer<A> {
+        Stealer {
+            deque: self.deque.clone(),
+        }
+    }
+
+    /// The maximum number of elements this deque can hold.
+    #[inline]
+    #[must_use]
+    pub fn capacity() -> usize {
+        A::CAPACITY
+    }
+
+    /// Get the number of elements in this deque.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        lock(&self.deque).len()
+    }
+
+    /// Tell whether this deque is empty.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        lock(&self.deque).is_empty()
+    }
+
+    /// Push an element onto the back of this deque.
+    ///
+    /// # Errors
+    ///
+    /// If the deque is full, a `CapacityError` holding the element is returned.
+    #[inline]
+    pub fn push(&self, element: A::Item) -> Result<(), CapacityError<A::Item>> {
+        lock(&self.deque).try_push_back(element)
+    }
+
+    /// Pop the most recently pushed element from the back of this deque.
+    #[inline]
+    #[must_use]
+    pub fn pop(&self) -> Option<A::Item> {
+        lock(&self.deque).pop_back()
+    }
+}
+
+impl<A: Array> Default for Worker<A> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: Array> fmt::Debug for Worker<A> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Worker { .. }")
+    }
+}
+
+/// A handle that other threads use to steal elements from the front of a `Worker`'s deque.
+pub struct Stealer<A: Array> {
+    deque: Arc<Mutex<ArrayDeque<A>>>,
+}
+
+impl<A: Array> Stealer<A> {
+    /// Try to steal the oldest element from the front of the deque.
+    #[inline]
+    #[must_use]
+    pub fn steal(&self) -> Steal<A::Item> {
+        let Some(mut deque) = try_lock(&self.deque) else {
+            return Steal::Retry;
+        };
+
+        match deque.pop_front() {
+            Some(element) => Steal::Success(element),
+            None => Steal::Empty,
+        }
+    }
+
+    /// Steal the oldest element from the front of the deque, waiting for the deque if it is
+    /// busy.
+    #[inline]
+    #[must_use]
+    pub fn steal_blocking(&self) -> Option<A::Item> {
+        lock(&self.deque).pop_front()
+    }
+
+    /// Steal up to half of the elements in the deque, pushing them onto the back of `dest`, and
+    /// pop one of them.
+    ///
+    /// Elements are moved oldest first, and the last of them is returned rather than pushed, so
+    /// at least one element can be stolen even if `dest` is full.
+    #[inline]
+    pub fn steal_batch_and_pop(&self, dest: &Worker<A>) -> Steal<A::Item> {
+        if Arc::ptr_eq(&self.deque, &dest.deque) {
+            return self.steal();
+        }
+
+        // waiting on either lock while holding the other could deadlock against a thief
+        // stealing in the opposite direction
+        let (Some(mut source), Some(mut dest)) = (try_lock(&self.deque), try_lock(&dest.deque))
+        else {
+            return Steal::Retry;
+        };
+
+        let batch = source.len().div_ceil(2).min(A::CAPACITY - dest.len() + 1);
+        if batch == 0 {
+            return Steal::Empty;
+        }
+
+        for _ in 1..batch {
+            if let Some(element) = source.pop_front() {
+                dest.push_back(element);
+            }
+        }
+        source.pop_front().map_or(Steal::Empty, Steal::Success)
+    }
+
+    /// Tell whether the deque is empty.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        lock(&self.deque).is_empty()
+    }
+
+    /// Get the number of elements in the deque.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        lock(&self.deque).len()
+    }
+}
+
+impl<A: Array> Clone for Stealer<A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            deque: self.deque.clone(),
+        }
+    }
+}
+
+impl<A: Array> fmt::Debug for Stealer<A> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Stealer { .. }")
+    }
+}
+
+#[test]
+fn test_steal_across_threads() {
+    use std::{thread, vec::Vec};
+
+    let worker: Worker<[u32; 64]> = Worker::new();
+    for job in 0..64 {
+        worker.push(job).unwrap();
+    }
+    assert_eq!(worker.push(64).unwrap_err().into_inner(), 64);
+
+    // owners see their own jobs last in, first out, and thieves first in, first out
+    let stealer = worker.stealer();
+    assert_eq!(worker.pop(), Some(63));
+    assert_eq!(stealer.steal(), Steal::Success(0));
+
+    let other: Worker<[u32; 64]> = Worker::new();
+    assert_eq!(stealer.steal_batch_and_pop(&other), Steal::Success(31));
+    assert_eq!(other.len(), 30);
+    assert_eq!(other.stealer().steal(), Steal::Success(1));
+
+    let thieves: Vec<_> = (0..4)
+        .map(|_| {
+            let stealer = stealer.clone();
+            thread::spawn(move || {
+                let mut stolen = Vec::new();
+                loop {
+                    match stealer.steal() {
+                        Steal::Success(job) => stolen.push(job),
+                        Steal::Retry => thread::yield_now(),
+                        Steal::Empty => return stolen,
+                    }
+                }
+            })
+        })
+        .collect();
+
+    let mut seen: Vec<u32> = core::iter::from_fn(|| worker.pop()).collect();
+    for thief in thieves {
+        seen.extend(thief.join().unwrap());
+    }
+    seen.sort_unstable();
+    assert!(seen.iter().copied().eq(32..63));
+}
@@ -0,0 +1,420 @@
+// MIT/Apache2 License
+
+//! A ring buffer of text, for scrollback buffers and console logs.
+
+use super::{array_deque::ArrayDeque, error::CapacityError};
+use core::{
+    fmt,
+    iter::{Chain, FusedIterator},
+    str::{self, Chars},
+};
+use tinyvec::Array;
+
+/// A fixed-size ring buffer of bytes that always holds valid UTF-8.
+///
+/// Characters are only ever added and removed whole. The end of the array never falls inside a
+/// character, so the contents can always be borrowed as two `str`s.
+///
+/// # Example
+///
+/// ```
+/// use tinydeque::str_deque::StrDeque;
+///
+/// let mut log: StrDeque<[u8; 20]> = StrDeque::new();
+/// log.push_str("boot ok\n");
+/// log.push_str("temp 21°C\n");
+/// assert_eq!(log.lines().count(), 2);
+///
+/// // keep the most recent text, dropping whole characters from the front
+/// log.push_str_overwrite("fan on\n");
+/// let lines: Vec<String> = log.lines().map(|line| line.to_string()).collect();
+/// assert_eq!(lines, ["k", "temp 21°C", "fan on"]);
+/// ```
+pub struct StrDeque<A: Array<Item = u8>> {
+    bytes: ArrayDeque<A>,
+}
+
+impl<A: Array<Item = u8>> StrDeque<A> {
+    /// Create a new, empty `StrDeque`.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            bytes: ArrayDeque::new(),
+        }
+    }
+
+    /// The maximum number of bytes this `StrDeque` can hold.
+    #[inline]
+    #[must_use]
+    pub fn capacity() -> usize {
+        A::CAPACITY
+    }
+
+    /// Get the length of the contents of this `StrDeque`, in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Tell whether this `StrDeque` is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Remove all of the text in this `StrDeque`.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+    }
+
+    /// Get the underlying `ArrayDeque` of bytes.
+    #[inline]
+    pub fn as_deque(&self) -> &ArrayDeque<A> {
+        &self.bytes
+    }
+
+    /// Get the contents of this `StrDeque` as two `str`s. The second one is empty unless the
+    /// contents wrap around the end of the array.
+    #[inline]
+    pub fn as_strs(&self) -> (&str, &str) {
+        let (front, back) = self.bytes.as_slices();
+        match (str::from_utf8(front), str::from_utf8(back)) {
+            (Ok(front), Ok(back)) => (front, back),
+            _ => unreachable!("<StrDeque> The contents are always valid UTF-8"),
+        }
+    }
+
+    /// Iterate over the characters in this `StrDeque`.
+    #[inline]
+    pub fn chars(&self) -> Chain<Chars<'_>, Chars<'_>> {
+        let (front, back) = self.as_strs();
+        front.chars().chain(back.chars())
+    }
+
+    /// Iterate over the lines in this `StrDeque`, including a line that wraps around the end
+    /// of the array.
+    ///
+    /// Lines are split the same way as [`str::lines`]: they end with `\n` or `\r\n`, which is not
+    /// included, and a final line does not need an ending.
+    #[inline]
+    pub fn lines(&self) -> Lines<'_> {
+        let (front, back) = self.as_strs();
+        Lines { front, back }
+    }
+
+    /// Try to push a string onto the back of this `StrDeque`.
+    ///
+    /// # Errors
+    ///
+    /// If the whole string does not fit, a `CapacityError` holding the string is returned and
+    /// nothing is pushed, so a character is never split.
+    #[inline]
+    pub fn try_push_str<'a>(&mut self, string: &'a str) -> Result<(), CapacityError<&'a str>> {
+        let (before_wrap, after_wrap) = self.bytes.spare_capacity_mut();
+        if string.len() > before_wrap.len() + after_wrap.len() {
+            return Err(CapacityError::new(string));
+        }
+
+        // the string would be split inside of a character, so make the free space contiguous
+        if string.len() > before_wrap.len() && !string.is_char_boundary(before_wrap.len()) {
+            self.bytes.rotate_to_front();
+        }
+        self.bytes.extend_from_slice(string.as_bytes());
+        Ok(())
+    }
+
+    /// Push a string onto the back of this `StrDeque`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the string does not fit.
+    #[inline]
+    pub fn push_str(&mut self, string: &str) {
+        if let Err(_) = self.try_push_str(string) {
+            panic!("<StrDeque> Unable to push string onto StrDeque, since it does not fit");
+        }
+    }
+
+    /// Push a string onto the back of this `StrDeque`, removing characters from the front until
+    /// it fits.
+    ///
+    /// If the string is longer than the capacity, only as many of its last characters as fit are
+    /// kept.
+    #[inline]
+    pub fn push_str_overwrite(&mut self, mut string: &str) {
+        if string.len() > Self::capacity() {
+            let start = (string.len() - Self::capacity()..string.len())
+                .find(|&i| string.is_char_boundary(i))
+                .unwrap_or(string.len());
+            string = &string[start..];
+            self.clear();
+        }
+
+        while Self::capacity() - self.len() < string.len() {
+            self.pop_char_front();
+        }
+        self.push_str(string);
+    }
+
+    /// Remove the first character of this `StrDeque`.
+    #[inline]
+    pub fn pop_char_front(&mut self) -> Option<char> {
+        let width = utf8_width(*self.bytes.front()?);
+        let mut encoded = [0; 4];
+        for byte in &mut encoded[..width] {
+            *byte = self.bytes.pop_front()?;
+        }
+        decode(&encoded[..width])
+    }
+
+    /// Remove the last character of this `StrDeque`.
+    #[inline]
+    pub fn pop_char_back(&mut self) -> Option<char> {
+        let mut encoded = [0; 4];
+        let mut width = 0;
+        while width < 4 {
+            let byte = self.bytes.pop_back()?;
+            width += 1;
+            encoded[4 - width] = byte;
+            if !is_continuation(byte) {
+                break;
+            }
+        }
+        decode(&encoded[4 - width..])
+    }
+}
+
+/// The length of a UTF-8 encoded character, from its first byte.
+#[inline]
+fn utf8_width(first: u8) -> usize {
+    match first {
+        0..=0x7F => 1,
+        0x80..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xFF => 4,
+    }
+}
+
+#[inline]
+fn is_continuation(byte: u8) -> bool {
+    byte & 0xC0 == 0x80
+}
+
+#[inline]
+fn decode(encoded: &[u8]) -> Option<char> {
+    str::from_utf8(encoded).ok()?.chars().next()
+}
+
+impl<A: Array<Item = u8>> Default for StrDeque<A> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: Array<Item = u8>> Clone for StrDeque<A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            bytes: self.bytes.clone(),
+        }
+    }
+}
+
+impl<A: Array<Item = u8>> fmt::Debug for StrDeque<A> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("\"")?;
+        for c in self.chars() {
+            fmt::Display::fmt(&c.escape_debug(), f)?;
+        }
+        f.write_str("\"")
+    }
+}
+
+impl<A: Array<Item = u8>> fmt::Display for StrDeque<A> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (front, back) = self.as_strs();
+        f.write_str(front)?;
+        f.write_str(back)
+    }
+}
+
+/// Writing fails without writing anything if the whole string does not fit.
+impl<A: Array<Item = u8>> fmt::Write for StrDeque<A> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.try_push_str(s).map_err(|_| fmt::Error)
+    }
+}
+
+/// A line of a `StrDeque`, which may be split in two by the end of the array.
+#[derive(Debug, Clone, Copy)]
+pub struct Line<'a> {
+    front: &'a str,
+    back: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// Get the line as two `str`s.
+    #[inline]
+    #[must_use]
+    pub fn as_strs(&self) -> (&'a str, &'a str) {
+        (self.front, self.back)
+    }
+
+    /// Get the length of the line, in bytes.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    /// Tell whether the line is empty.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterate over the characters in the line.
+    #[inline]
+    pub fn chars(&self) -> Chain<Chars<'a>, Chars<'a>> {
+        self.front.chars().chain(self.back.chars())
+    }
+}
+
+impl fmt::Display for Line<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.front)?;
+        f.write_str(self.back)
+    }
+}
+
+impl PartialEq<str> for Line<'_> {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        other.len() == self.len() && other.starts_with(self.front) && other.ends_with(self.back)
+    }
+}
+
+impl PartialEq<&str> for Line<'_> {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+/// An iterator over the lines of a `StrDeque`.
+#[derive(Debug, Clone)]
+pub struct Lines<'a> {
+    front: &'a str,
+    back: &'a str,
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = Line<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Line<'a>> {
+        let (mut line, ended) = if let Some(end) = self.front.find('\n') {
+            let line = Line {
+                front: &self.front[..end],
+                back: "",
+            };
+            self.front = &self.front[end + 1..];
+            (line, true)
+        } else if self.front.is_empty() && self.back.is_empty() {
+            return None;
+        } else {
+            // the line runs on past the end of the array
+            let end = self.back.find('\n');
+            let line = Line {
+                front: self.front,
+                back: &self.back[..end.unwrap_or(self.back.len())],
+            };
+            self.front = end.map_or("", |end| &self.back[end + 1..]);
+            self.back = "";
+            (line, end.is_some())
+        };
+
+        // like `str::lines`, only a `\r` before a `\n` is part of the line ending
+        if ended {
+            if let Some(back) = line.back.strip_suffix('\r') {
+                line.back = back;
+            } else if line.back.is_empty() {
+                line.front = line.front.strip_suffix('\r').unwrap_or(line.front);
+            }
+        }
+        Some(line)
+    }
+}
+
+impl FusedIterator for Lines<'_> {}
+
+#[test]
+fn test_str_deque_wrapped_chars() {
+    let mut text: StrDeque<[u8; 8]> = StrDeque::new();
+    text.push_str("abcdé");
+    assert_eq!(text.pop_char_front(), Some('a'));
+    assert_eq!(text.pop_char_front(), Some('b'));
+    assert_eq!(text.pop_char_front(), Some('c'));
+
+    // "€" does not fit before the end of the array in one piece, so the contents are moved
+    text.push_str("€");
+    assert_eq!(text.as_strs(), ("dé€", ""));
+    assert_eq!(text.try_push_str("€").unwrap_err().into_inner(), "€");
+
+    // splitting at a character boundary wraps as usual
+    assert_eq!(text.pop_char_front(), Some('d'));
+    text.push_str("\r\nx");
+    assert_eq!(text.as_strs(), ("é€\r\n", "x"));
+    assert!(text.lines().eq(["é€", "x"].iter().copied()));
+    assert_eq!(text.pop_char_back(), Some('x'));
+    assert_eq!(text.pop_char_back(), Some('\n'));
+    assert_eq!(text.pop_char_back(), Some('\r'));
+
+    // a line can run on past the end of the array
+    text.push_str("ab");
+    text.push_str("c");
+    assert_eq!(text.as_strs(), ("é€ab", "c"));
+    assert!(text.lines().eq(["é€abc"].iter().copied()));
+
+    text.push_str_overwrite("ünïcödé");
+    assert_eq!(text.len(), 8);
+    assert!(text.chars().eq("ïcödé".chars()));
+    assert_eq!(text.pop_char_back(), Some('é'));
+}
+
+#[test]
+fn test_str_deque_lines_carriage_return() {
+    let mut text: StrDeque<[u8; 8]> = StrDeque::new();
+    text.push_str("a\nb\r");
+    assert!(text.lines().eq(["a", "b\r"].iter().copied()));
+    text.pop_char_front();
+    text.pop_char_front();
+
+    // an unterminated `\r` right before the wrap point
+    text.push_str("cde\r");
+    assert_eq!(text.as_strs(), ("b\rcde\r", ""));
+    assert!(text.lines().eq(["b\rcde\r"].iter().copied()));
+    text.pop_char_front();
+    text.pop_char_front();
+
+    // a `\r\n` split by the wrap point, and an unterminated `\r` after it
+    text.push_str("\nx\r");
+    assert_eq!(text.as_strs(), ("cde\r", "\nx\r"));
+    assert!(text.lines().eq(["cde", "x\r"].iter().copied()));
+
+    // an unterminated `\r` at the end of a line that runs on past the wrap point
+    text.pop_char_back();
+    text.pop_char_back();
+    text.pop_char_back();
+    text.push_str("w\r");
+    assert_eq!(text.as_strs(), ("cde\r", "w\r"));
+    assert!(text.lines().eq(["cde\rw\r"].iter().copied()));
+}
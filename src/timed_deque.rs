@@ -0,0 +1,394 @@
+// MIT/Apache2 License
+
+//! Queues of timestamped entries, for sliding time windows such as rate limits.
+//!
+//! The instant type is left generic, so anything that is `Ord` can be used: a tick counter from a
+//! hardware timer, or a plain integer in tests.
+//!
+//! The entries are stored in an array, so the instant type must implement `Default`.
+//! `std::time::Instant` does not, but the `Duration` since a fixed starting `Instant` does:
+//!
+//! ```
+//! use std::time::{Duration, Instant};
+//! use tinydeque::timed_deque::RateLimiter;
+//!
+//! let start = Instant::now();
+//! let mut limiter: RateLimiter<[(Duration, ()); 2], Duration> =
+//!     RateLimiter::new(Duration::from_secs(1));
+//! assert!(limiter.try_acquire(start.elapsed()));
+//! ```
+
+use super::{
+    array_deque::{ArrayDeque, Drain, Iter as ArrayDequeIter},
+    error::CapacityError,
+};
+use core::{fmt, iter::FusedIterator, marker::PhantomData, ops::Sub};
+use tinyvec::Array;
+
+/// A fixed-size queue of `(instant, item)` pairs, oldest first.
+///
+/// Entries are expected to be pushed in order of their instants. This is checked in debug builds,
+/// and the queries below assume it.
+///
+/// # Example
+///
+/// ```
+/// use tinydeque::timed_deque::TimedDeque;
+///
+/// let mut logins: TimedDeque<[(u32, &str); 4], u32> = TimedDeque::new();
+/// logins.push_at(10, "alice");
+/// logins.push_at(25, "bob");
+/// logins.push_at(40, "carol");
+///
+/// assert_eq!(logins.count_since(&20), 2);
+/// assert_eq!(logins.oldest_age(50), Some(40));
+///
+/// let expired: Vec<_> = logins.expire_before(&30).collect();
+/// assert_eq!(expired, [(10, "alice"), (25, "bob")]);
+/// assert_eq!(logins.len(), 1);
+/// ```
+pub struct TimedDeque<A: Array, I> {
+    entries: ArrayDeque<A>,
+    _marker: PhantomData<fn() -> I>,
+}
+
+impl<I, T, A: Array<Item = (I, T)>> Default for TimedDeque<A, I> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Clone, T: Clone, A: Array<Item = (I, T)>> Clone for TimedDeque<A, I> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            entries: self.entries.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<I: fmt::Debug, T: fmt::Debug, A: Array<Item = (I, T)>> fmt::Debug for TimedDeque<A, I> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<I, T, A: Array<Item = (I, T)>> TimedDeque<A, I> {
+    /// Create a new, empty `TimedDeque`.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            entries: ArrayDeque::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// The maximum number of entries this `TimedDeque` can hold.
+    #[inline]
+    #[must_use]
+    pub fn capacity() -> usize {
+        A::CAPACITY
+    }
+
+    /// Get the number of entries in this `TimedDeque`.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Tell whether this `TimedDeque` is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Tell whether this `TimedDeque` is full.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.entries.is_full()
+    }
+
+    /// Get the oldest entry.
+    #[inline]
+    pub fn oldest(&self) -> Option<(&I, &T)> {
+        self.entries.front().map(|(i, t)| (i, t))
+    }
+
+    /// Get the newest entry.
+    #[inline]
+    pub fn newest(&self) -> Option<(&I, &T)> {
+        self.entries.back().map(|(i, t)| (i, t))
+    }
+
+    /// Remove and return the oldest entry.
+    #[inline]
+    pub fn pop_oldest(&mut self) -> Option<(I, T)> {
+        self.entries.pop_front()
+    }
+
+    /// Remove every entry from this `TimedDeque`.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Iterate over the entries in this `TimedDeque`, from oldest to newest.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, I, T, A> {
+        Iter {
+            inner: self.entries.iter(),
+        }
+    }
+
+    /// Get the underlying `ArrayDeque`.
+    #[inline]
+    pub fn as_deque(&self) -> &ArrayDeque<A> {
+        &self.entries
+    }
+}
+
+impl<I: Ord, T, A: Array<Item = (I, T)>> TimedDeque<A, I> {
+    #[inline]
+    fn debug_check_order(&self, now: &I) {
+        if let Some((newest, _)) = self.entries.back() {
+            debug_assert!(
+                newest <= now,
+                "<TimedDeque> Entries must be pushed in order of their instants"
+            );
+        }
+    }
+
+    /// Try to push an entry that happened at `now`.
+    ///
+    /// # Errors
+    ///
+    /// If the `TimedDeque` is full, a `CapacityError` holding the entry is returned.
+    #[inline]
+    pub fn try_push_at(&mut self, now: I, item: T) -> Result<(), CapacityError<(I, T)>> {
+        self.debug_check_order(&now);
+        self.entries.try_push_back((now, item))
+    }
+
+    /// Push an entry that happened at `now`, evicting the oldest entry if the `TimedDeque` is
+    /// full.
+    ///
+    /// The evicted entry, if any, is returned.
+    #[inline]
+    pub fn push_at(&mut self, now: I, item: T) -> Option<(I, T)> {
+        self.debug_check_order(&now);
+        self.entries.push_back_overwrite((now, item))
+    }
+
+    /// Remove every entry that happened strictly before `deadline`.
+    ///
+    /// The expired entries are yielded from oldest to newest by the returned iterator, and are
+    /// removed even if it is not consumed.
+    #[inline]
+    pub fn expire_before(&mut self, deadline: &I) -> Drain<'_, A> {
+        let expired = self
+            .entries
+            .iter()
+            .take_while(|(instant, _)| instant < deadline)
+            .count();
+        self.entries.drain(..expired)
+    }
+
+    /// Count the entries that happened at or after `since`.
+    #[inline]
+    pub fn count_since(&self, since: &I) -> usize {
+        self.entries
+            .iter()
+            .rev()
+            .take_while(|(instant, _)| instant >= since)
+            .count()
+    }
+
+    /// Get the time elapsed between the oldest entry and `now`, or `None` if there are no
+    /// entries or `now` is before the oldest entry.
+    #[inline]
+    pub fn oldest_age(&self, now: I) -> Option<I::Output>
+    where
+        I: Clone + Sub,
+    {
+        match self.entries.front() {
+            Some((oldest, _)) if *oldest <= now => Some(now - oldest.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl<'a, I, T: 'a, A: Array<Item = (I, T)>> IntoIterator for &'a TimedDeque<A, I> {
+    type Item = (&'a I, &'a T);
+    type IntoIter = Iter<'a, I, T, A>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the entries of a `TimedDeque`, from oldest to newest.
+pub struct Iter<'a, I: 'a, T: 'a, A: Array<Item = (I, T)> + 'a> {
+    inner: ArrayDequeIter<'a, A>,
+}
+
+impl<'a, I, T, A: Array<Item = (I, T)>> Iterator for Iter<'a, I, T, A> {
+    type Item = (&'a I, &'a T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(i, t)| (i, t))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<I, T, A: Array<Item = (I, T)>> DoubleEndedIterator for Iter<'_, I, T, A> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(i, t)| (i, t))
+    }
+}
+
+impl<I, T, A: Array<Item = (I, T)>> ExactSizeIterator for Iter<'_, I, T, A> {}
+
+impl<I, T, A: Array<Item = (I, T)>> FusedIterator for Iter<'_, I, T, A> {}
+
+/// A sliding-window rate limiter, allowing at most `A::CAPACITY` events per window.
+///
+/// # Example
+///
+/// ```
+/// use tinydeque::timed_deque::RateLimiter;
+///
+/// // three requests every ten ticks
+/// let mut limiter: RateLimiter<[(u64, ()); 3], u64> = RateLimiter::new(10);
+/// assert!(limiter.try_acquire(0));
+/// assert!(limiter.try_acquire(1));
+/// assert!(limiter.try_acquire(2));
+/// assert!(!limiter.try_acquire(5));
+///
+/// // the first request has left the window
+/// assert!(limiter.try_acquire(10));
+/// assert_eq!(limiter.available(&10), 0);
+/// assert_eq!(limiter.available(&11), 1);
+/// ```
+pub struct RateLimiter<A: Array<Item = (I, ())>, I: Sub> {
+    events: TimedDeque<A, I>,
+    window: I::Output,
+}
+
+impl<A, I> RateLimiter<A, I>
+where
+    A: Array<Item = (I, ())>,
+    I: Ord + Clone + Sub,
+    I::Output: PartialOrd,
+{
+    /// Create a new `RateLimiter` with the given window length.
+    #[inline]
+    pub fn new(window: I::Output) -> Self {
+        Self {
+            events: TimedDeque::new(),
+            window,
+        }
+    }
+
+    /// Get the length of the window.
+    #[inline]
+    pub fn window(&self) -> &I::Output {
+        &self.window
+    }
+
+    /// Forget every event that has left the window as of `now`.
+    #[inline]
+    fn expire(&mut self, now: &I) {
+        while let Some((oldest, ())) = self.events.oldest() {
+            // an event after `now` means the clock went backwards, so it is still in the window
+            if now < oldest || now.clone() - oldest.clone() < self.window {
+                break;
+            }
+            self.events.pop_oldest();
+        }
+    }
+
+    /// Get the number of events that would be allowed at `now`.
+    #[inline]
+    pub fn available(&mut self, now: &I) -> usize {
+        self.expire(now);
+        A::CAPACITY - self.events.len()
+    }
+
+    /// Record an event at `now` if the limit allows it, and tell whether it was allowed.
+    ///
+    /// If the clock has gone backwards since the last event, the event is recorded at the
+    /// newest instant instead, so the events stay in order.
+    #[inline]
+    pub fn try_acquire(&mut self, now: I) -> bool {
+        let now = match self.events.newest() {
+            Some((newest, ())) if *newest > now => newest.clone(),
+            _ => now,
+        };
+        self.expire(&now);
+        self.events.try_push_at(now, ()).is_ok()
+    }
+
+    /// Forget every recorded event.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.events.clear();
+    }
+
+    /// Get the events currently inside the window.
+    #[inline]
+    pub fn events(&self) -> &TimedDeque<A, I> {
+        &self.events
+    }
+}
+
+#[test]
+fn test_timed_deque_expiry() {
+    let mut deque: TimedDeque<[(u32, char); 4], u32> = TimedDeque::new();
+    // wrap the ring buffer around before checking expiry
+    for (now, c) in (0..6).zip("abcdef".chars()) {
+        deque.push_at(now * 10, c);
+    }
+    assert_eq!(deque.oldest(), Some((&20, &'c')));
+    assert_eq!(
+        deque.try_push_at(60, 'g').unwrap_err().into_inner(),
+        (60, 'g')
+    );
+
+    assert_eq!(deque.count_since(&30), 3);
+    assert_eq!(deque.count_since(&51), 0);
+    assert_eq!(deque.expire_before(&0).len(), 0);
+
+    // dropping the drain still removes the expired entries
+    drop(deque.expire_before(&40));
+    assert!(deque.iter().eq([(&40, &'e'), (&50, &'f')].iter().copied()));
+    assert_eq!(deque.oldest_age(55), Some(15));
+
+    assert_eq!(deque.oldest_age(35), None);
+
+    assert_eq!(deque.expire_before(&100).count(), 2);
+    assert_eq!(deque.oldest_age(55), None);
+
+    // a clock that goes backwards must not underflow the unsigned instants
+    let mut limiter: RateLimiter<[(u32, ()); 2], u32> = RateLimiter::new(10);
+    assert!(limiter.try_acquire(100));
+    assert_eq!(limiter.available(&90), 1);
+    assert!(limiter.try_acquire(95));
+    assert!(!limiter.try_acquire(90));
+    assert!(limiter
+        .events()
+        .iter()
+        .map(|(now, ())| *now)
+        .eq([100, 100].iter().copied()));
+    assert_eq!(limiter.available(&110), 2);
+}
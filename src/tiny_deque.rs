@@ -2,10 +2,20 @@
 
 #![cfg(feature = "alloc")]
 
-use super::array_deque::{ArrayDeque, Iter as ArrayDequeIter};
-use alloc::collections::vec_deque::{Iter as VecDequeIter, VecDeque};
-use core::iter::FromIterator;
-use tinyvec::Array;
+use super::array_deque::{cursor_next, cursor_prev, ArrayDeque, Iter as ArrayDequeIter};
+use super::{
+    array_deque::Drain as ArrayDequeDrain,
+    chunks::{Chunks, ChunksExact, Windows},
+    deque_slice::{DequeSlice, DequeSliceMut},
+};
+use alloc::{
+    collections::vec_deque::{Drain as VecDequeDrain, Iter as VecDequeIter, VecDeque},
+    vec::Vec,
+};
+use core::{fmt, iter::FromIterator, ops::RangeBounds};
+#[cfg(feature = "std")]
+use std::io::IoSlice;
+use tinyvec::{Array, ArrayVec, TinyVec};
 
 /// A deque structure that can overflow onto the heap if it spills the stack.
 pub enum TinyDeque<A: Array> {
@@ -15,19 +25,25 @@ pub enum TinyDeque<A: Array> {
 
 impl<A: Array> TinyDeque<A> {
     /// Create a new `TinyDeque`.
+    ///
+    /// If the array has a capacity of zero, the `TinyDeque` starts out on the heap and behaves
+    /// like a `VecDeque`. Creating it does not allocate.
     #[inline]
+    #[must_use]
     pub fn new() -> Self {
-        Self::Stack(ArrayDeque::new())
+        Self::with_capacity(0)
     }
 
     /// Create a new `TinyDeque` with the specified capacity. If the capacity is greater
-    /// than the array capacity, it will spill onto the heap.
+    /// than the array capacity, or the array has a capacity of zero, it will spill onto the
+    /// heap.
     #[inline]
+    #[must_use]
     pub fn with_capacity(cap: usize) -> Self {
-        if cap > A::CAPACITY {
-            Self::Stack(ArrayDeque::new())
-        } else {
+        if cap > A::CAPACITY || A::CAPACITY == 0 {
             Self::Heap(VecDeque::with_capacity(cap))
+        } else {
+            Self::Stack(ArrayDeque::new())
         }
     }
 
@@ -57,7 +73,7 @@ impl<A: Array> TinyDeque<A> {
             Self::Stack(s) => {
                 if let Err(reject) = s.try_push_back(element) {
                     self.spill();
-                    self.as_heap_mut().push_back(reject);
+                    self.as_heap_mut().push_back(reject.into_inner());
                 }
             }
         }
@@ -67,11 +83,11 @@ impl<A: Array> TinyDeque<A> {
     #[inline]
     pub fn push_front(&mut self, element: A::Item) {
         match self {
-            Self::Heap(v) => v.push_back(element),
+            Self::Heap(v) => v.push_front(element),
             Self::Stack(s) => {
-                if let Err(reject) = s.try_push_back(element) {
+                if let Err(reject) = s.try_push_front(element) {
                     self.spill();
-                    self.as_heap_mut().push_back(reject);
+                    self.as_heap_mut().push_front(reject.into_inner());
                 }
             }
         }
@@ -146,6 +162,132 @@ impl<A: Array> TinyDeque<A> {
         }
     }
 
+    /// Move the contents of this `TinyDeque` so that they are in one slice, and return it.
+    #[inline]
+    pub fn make_contiguous(&mut self) -> &mut [A::Item] {
+        match self {
+            Self::Heap(v) => v.make_contiguous(),
+            Self::Stack(s) => s.make_contiguous(),
+        }
+    }
+
+    /// Get the contents of this byte `TinyDeque` as two `IoSlice`s, for vectored writes. The
+    /// second one is empty unless the contents wrap around.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn as_io_slices(&self) -> [IoSlice<'_>; 2]
+    where
+        A: Array<Item = u8>,
+    {
+        let (front, back) = self.as_slices();
+        [IoSlice::new(front), IoSlice::new(back)]
+    }
+
+    /// Get the unused slots of this `TinyDeque` as at most two mutable slices, or `None` if it
+    /// has spilled onto the heap. See [`ArrayDeque::spare_capacity_mut`].
+    ///
+    /// The spare capacity of a `VecDeque` is uninitialized memory, which cannot be handed out
+    /// without `unsafe` code. Rather than returning two empty slices, which a vectored read would
+    /// take as the end of the stream, a spilled `TinyDeque` returns `None`, and data has to be
+    /// pushed or extended onto it instead. A `Some` with two empty slices means the deque is
+    /// still on the stack and full. With the `bytes` feature, the `BufMut` implementation can
+    /// write into the spare capacity of a spilled byte `TinyDeque` as well.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::io::{IoSliceMut, Read};
+    /// # use tinydeque::TinyDeque;
+    /// fn fill(deque: &mut TinyDeque<[u8; 8]>, mut socket: &[u8]) -> usize {
+    ///     match deque.spare_capacity_mut() {
+    ///         Some((front, back)) => {
+    ///             let read = socket
+    ///                 .read_vectored(&mut [IoSliceMut::new(front), IoSliceMut::new(back)])
+    ///                 .unwrap();
+    ///             deque.commit_back(read);
+    ///             read
+    ///         }
+    ///         None => {
+    ///             let mut buf = [0; 64];
+    ///             let read = socket.read(&mut buf).unwrap();
+    ///             deque.extend(buf[..read].iter().copied());
+    ///             read
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let mut deque = TinyDeque::new();
+    /// assert_eq!(fill(&mut deque, b"hello world"), 8);
+    /// assert_eq!(fill(&mut deque, b"rld"), 0);
+    /// deque.push_back(b'r');
+    /// assert!(matches!(deque, TinyDeque::Heap(_)));
+    /// assert_eq!(fill(&mut deque, b"ld"), 2);
+    /// assert!(deque.iter().eq(b"hello world".iter()));
+    /// ```
+    #[inline]
+    #[allow(clippy::type_complexity)]
+    pub fn spare_capacity_mut(&mut self) -> Option<(&mut [A::Item], &mut [A::Item])> {
+        match self {
+            Self::Heap(_) => None,
+            Self::Stack(s) => Some(s.spare_capacity_mut()),
+        }
+    }
+
+    /// Add the first `n` slots returned by
+    /// [`spare_capacity_mut`](TinyDeque::spare_capacity_mut) to the back of this `TinyDeque`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `n` is greater than the number of unused slots, which is
+    /// always zero once the `TinyDeque` has spilled onto the heap.
+    #[inline]
+    pub fn commit_back(&mut self, n: usize) {
+        match self {
+            Self::Heap(_) => assert!(
+                n == 0,
+                "<TinyDeque> Cannot commit more slots than are unused"
+            ),
+            Self::Stack(s) => s.commit_back(n),
+        }
+    }
+
+    /// Get the contents of this `TinyDeque` as a single [`DequeSlice`].
+    #[inline]
+    pub fn as_deque_slice(&self) -> DequeSlice<'_, A::Item> {
+        let (front, back) = self.as_slices();
+        DequeSlice::new(front, back)
+    }
+
+    /// Get the contents of this `TinyDeque` as a single [`DequeSliceMut`].
+    #[inline]
+    pub fn as_deque_slice_mut(&mut self) -> DequeSliceMut<'_, A::Item> {
+        let (front, back) = self.as_mut_slices();
+        DequeSliceMut::new(front, back)
+    }
+
+    /// Get a [`DequeSlice`] over a logical range of this `TinyDeque`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the range is out of bounds.
+    #[inline]
+    pub fn range<R: RangeBounds<usize>>(&self, range: R) -> DequeSlice<'_, A::Item> {
+        self.as_deque_slice().range(range)
+    }
+
+    /// Remove a logical range of elements from this `TinyDeque`, returning them as an iterator.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the range is out of bounds.
+    #[inline]
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, A> {
+        match self {
+            Self::Heap(v) => Drain::Heap(v.drain(range)),
+            Self::Stack(s) => Drain::Stack(s.drain(range)),
+        }
+    }
+
     /// Create an iterator.
     #[inline]
     pub fn iter(&self) -> Iter<'_, A> {
@@ -155,6 +297,151 @@ impl<A: Array> TinyDeque<A> {
         }
     }
 
+    /// Iterate over `size`-element chunks of this deque, starting at the front.
+    ///
+    /// Each chunk is yielded as a [`DequeSlice`]. The last chunk may be shorter than `size`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `size` is zero.
+    #[inline]
+    pub fn chunks(&self, size: usize) -> Chunks<'_, A::Item> {
+        Chunks::new(self.as_deque_slice(), size)
+    }
+
+    /// Iterate over `size`-element chunks of this deque, starting at the front and skipping any
+    /// remainder at the back.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `size` is zero.
+    #[inline]
+    pub fn chunks_exact(&self, size: usize) -> ChunksExact<'_, A::Item> {
+        ChunksExact::new(self.as_deque_slice(), size)
+    }
+
+    /// Iterate over all overlapping `size`-element windows of this deque.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `size` is zero.
+    #[inline]
+    pub fn windows(&self, size: usize) -> Windows<'_, A::Item> {
+        Windows::new(self.as_deque_slice(), size)
+    }
+
+    /// Swap the elements at indices `i` and `j`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if either index is out of bounds.
+    #[inline]
+    pub fn swap(&mut self, i: usize, j: usize) {
+        match self {
+            Self::Heap(v) => v.swap(i, j),
+            Self::Stack(s) => s.swap(i, j),
+        }
+    }
+
+    /// Insert an element at the given index, spilling onto the heap if necessary.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `index` is greater than the length of the deque.
+    #[inline]
+    pub fn insert(&mut self, index: usize, element: A::Item) {
+        match self {
+            Self::Heap(v) => v.insert(index, element),
+            Self::Stack(s) => {
+                if let Err(reject) = s.try_insert(index, element) {
+                    self.spill();
+                    self.as_heap_mut().insert(index, reject.into_inner());
+                }
+            }
+        }
+    }
+
+    /// Remove the element at the given index. Returns `None` if the index is out of bounds.
+    #[inline]
+    pub fn remove(&mut self, index: usize) -> Option<A::Item> {
+        match self {
+            Self::Heap(v) => v.remove(index),
+            Self::Stack(s) => s.remove(index),
+        }
+    }
+
+    /// Rotate this deque `n` places to the left, so that the element at index `n` becomes the
+    /// front element.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `n` is greater than the length of the deque.
+    #[inline]
+    pub fn rotate_left(&mut self, n: usize) {
+        match self {
+            Self::Heap(v) => v.rotate_left(n),
+            Self::Stack(s) => s.rotate_left(n),
+        }
+    }
+
+    /// Rotate this deque `n` places to the right, so that the element at index `len - n`
+    /// becomes the front element.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `n` is greater than the length of the deque.
+    #[inline]
+    pub fn rotate_right(&mut self, n: usize) {
+        match self {
+            Self::Heap(v) => v.rotate_right(n),
+            Self::Stack(s) => s.rotate_right(n),
+        }
+    }
+
+    /// Get a cursor pointing at the front element of this deque.
+    ///
+    /// If the deque is empty, the cursor points at the "ghost" position.
+    #[inline]
+    pub fn cursor_front(&self) -> Cursor<'_, A> {
+        Cursor {
+            index: 0,
+            deque: self,
+        }
+    }
+
+    /// Get a cursor pointing at the back element of this deque.
+    ///
+    /// If the deque is empty, the cursor points at the "ghost" position.
+    #[inline]
+    pub fn cursor_back(&self) -> Cursor<'_, A> {
+        Cursor {
+            index: self.len().saturating_sub(1),
+            deque: self,
+        }
+    }
+
+    /// Get a mutable cursor pointing at the front element of this deque.
+    ///
+    /// If the deque is empty, the cursor points at the "ghost" position.
+    #[inline]
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, A> {
+        CursorMut {
+            index: 0,
+            deque: self,
+        }
+    }
+
+    /// Get a mutable cursor pointing at the back element of this deque.
+    ///
+    /// If the deque is empty, the cursor points at the "ghost" position.
+    #[inline]
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, A> {
+        CursorMut {
+            index: self.len().saturating_sub(1),
+            deque: self,
+        }
+    }
+
     #[inline]
     fn as_heap_mut(&mut self) -> &mut VecDeque<A::Item> {
         match self {
@@ -164,7 +451,7 @@ impl<A: Array> TinyDeque<A> {
     }
 
     #[inline]
-    fn spill(&mut self) {
+    pub(crate) fn spill(&mut self) {
         let stack = match self {
             Self::Heap(_) => return,
             Self::Stack(ref mut s) => s,
@@ -177,6 +464,52 @@ impl<A: Array> TinyDeque<A> {
     }
 }
 
+impl<A: Array> Default for TinyDeque<A> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The contents are printed in logical order, wrapped in `Stack` or `Heap` to show where they
+/// are stored.
+///
+/// # Example
+///
+/// ```
+/// use tinydeque::TinyDeque;
+///
+/// let mut deque: TinyDeque<[u8; 2]> = TinyDeque::new();
+/// deque.push_back(0);
+/// deque.push_back(1);
+/// assert_eq!(format!("{:?}", deque), "Stack([0, 1])");
+///
+/// deque.push_back(2);
+/// assert_eq!(format!("{:?}", deque), "Heap([0, 1, 2])");
+/// ```
+impl<A: Array> fmt::Debug for TinyDeque<A>
+where
+    A::Item: fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Stack(_) => "Stack",
+            Self::Heap(_) => "Heap",
+        };
+        f.debug_tuple(name).field(&self.as_deque_slice()).finish()
+    }
+}
+
+/// Bytes can be written into a `TinyDeque`, spilling onto the heap as needed.
+impl<A: Array<Item = u8>> fmt::Write for TinyDeque<A> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        s.bytes().for_each(|byte| self.push_back(byte));
+        Ok(())
+    }
+}
+
 impl<A: Array> Clone for TinyDeque<A>
 where
     A::Item: Clone,
@@ -190,6 +523,16 @@ where
     }
 }
 
+impl<'a, A: Array> IntoIterator for &'a TinyDeque<A> {
+    type Item = &'a A::Item;
+    type IntoIter = Iter<'a, A>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a, A> {
+        self.iter()
+    }
+}
+
 impl<A: Array> FromIterator<A::Item> for TinyDeque<A> {
     #[inline]
     fn from_iter<T: IntoIterator<Item = A::Item>>(iter: T) -> Self {
@@ -207,6 +550,63 @@ impl<A: Array> Extend<A::Item> for TinyDeque<A> {
     }
 }
 
+impl<A: Array> From<ArrayDeque<A>> for TinyDeque<A> {
+    #[inline]
+    fn from(deque: ArrayDeque<A>) -> Self {
+        Self::Stack(deque)
+    }
+}
+
+impl<A: Array> From<ArrayVec<A>> for TinyDeque<A> {
+    /// Create a `TinyDeque` from an `ArrayVec`, reusing its storage without copying.
+    #[inline]
+    fn from(vec: ArrayVec<A>) -> Self {
+        Self::Stack(ArrayDeque::from(vec))
+    }
+}
+
+impl<A: Array> From<TinyVec<A>> for TinyDeque<A> {
+    /// Create a `TinyDeque` from a `TinyVec`, reusing its storage without copying.
+    #[inline]
+    fn from(vec: TinyVec<A>) -> Self {
+        match vec {
+            TinyVec::Inline(a) => Self::from(a),
+            TinyVec::Heap(v) => Self::from(v),
+        }
+    }
+}
+
+impl<A: Array> From<Vec<A::Item>> for TinyDeque<A> {
+    /// Create a `TinyDeque` on the heap from a `Vec`, reusing its storage without copying.
+    #[inline]
+    fn from(vec: Vec<A::Item>) -> Self {
+        Self::Heap(VecDeque::from(vec))
+    }
+}
+
+impl<A: Array> From<VecDeque<A::Item>> for TinyDeque<A> {
+    #[inline]
+    fn from(deque: VecDeque<A::Item>) -> Self {
+        Self::Heap(deque)
+    }
+}
+
+impl<A: Array> From<TinyDeque<A>> for VecDeque<A::Item> {
+    /// Convert a `TinyDeque` into a `VecDeque`. If the `TinyDeque` is on the heap, its storage
+    /// is reused without copying.
+    #[inline]
+    fn from(deque: TinyDeque<A>) -> Self {
+        match deque {
+            TinyDeque::Heap(v) => v,
+            TinyDeque::Stack(mut s) => {
+                let mut v = VecDeque::with_capacity(s.len());
+                v.extend(s.drain(..));
+                v
+            }
+        }
+    }
+}
+
 /// An iterator over the elements in a `TinyDeque`.
 pub enum Iter<'a, A: Array> {
     Stack(ArrayDequeIter<'a, A>),
@@ -233,7 +633,7 @@ impl<'a, A: Array> Iterator for Iter<'a, A> {
     }
 }
 
-impl<'a, A: Array> ExactSizeIterator for Iter<'a, A> {}
+impl<A: Array> ExactSizeIterator for Iter<'_, A> {}
 
 impl<'a, A: Array> DoubleEndedIterator for Iter<'a, A> {
     #[inline]
@@ -244,3 +644,248 @@ impl<'a, A: Array> DoubleEndedIterator for Iter<'a, A> {
         }
     }
 }
+
+/// A draining iterator over a range of a `TinyDeque`.
+pub enum Drain<'a, A: Array> {
+    Stack(ArrayDequeDrain<'a, A>),
+    Heap(VecDequeDrain<'a, A::Item>),
+}
+
+impl<A: Array> Iterator for Drain<'_, A> {
+    type Item = A::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<A::Item> {
+        match self {
+            Self::Stack(a) => a.next(),
+            Self::Heap(v) => v.next(),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Self::Stack(a) => a.size_hint(),
+            Self::Heap(v) => v.size_hint(),
+        }
+    }
+}
+
+impl<A: Array> ExactSizeIterator for Drain<'_, A> {}
+
+impl<A: Array> DoubleEndedIterator for Drain<'_, A> {
+    #[inline]
+    fn next_back(&mut self) -> Option<A::Item> {
+        match self {
+            Self::Stack(a) => a.next_back(),
+            Self::Heap(v) => v.next_back(),
+        }
+    }
+}
+
+/// A cursor over a `TinyDeque`.
+///
+/// This behaves like the `ArrayDeque` [`Cursor`](crate::array_deque::Cursor), including the
+/// "ghost" position between the back and the front.
+pub struct Cursor<'a, A: Array> {
+    index: usize,
+    deque: &'a TinyDeque<A>,
+}
+
+impl<A: Array> Clone for Cursor<'_, A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            index: self.index,
+            deque: self.deque,
+        }
+    }
+}
+
+impl<'a, A: Array> Cursor<'a, A> {
+    /// Get the index of the element the cursor points at, or `None` at the ghost position.
+    #[inline]
+    #[must_use]
+    pub fn index(&self) -> Option<usize> {
+        if self.index < self.deque.len() {
+            Some(self.index)
+        } else {
+            None
+        }
+    }
+
+    /// Move the cursor to the next element, or onto the ghost position from the back element.
+    #[inline]
+    pub fn move_next(&mut self) {
+        self.index = cursor_next(self.index, self.deque.len());
+    }
+
+    /// Move the cursor to the previous element, or onto the ghost position from the front
+    /// element.
+    #[inline]
+    pub fn move_prev(&mut self) {
+        self.index = cursor_prev(self.index, self.deque.len());
+    }
+
+    /// Get the element the cursor points at, or `None` at the ghost position.
+    #[inline]
+    #[must_use]
+    pub fn current(&self) -> Option<&'a A::Item> {
+        self.deque.get(self.index)
+    }
+
+    /// Get the element after the cursor. At the ghost position, this is the front element.
+    #[inline]
+    #[must_use]
+    pub fn peek_next(&self) -> Option<&'a A::Item> {
+        self.deque.get(cursor_next(self.index, self.deque.len()))
+    }
+
+    /// Get the element before the cursor. At the ghost position, this is the back element.
+    #[inline]
+    #[must_use]
+    pub fn peek_prev(&self) -> Option<&'a A::Item> {
+        self.deque.get(cursor_prev(self.index, self.deque.len()))
+    }
+}
+
+/// A mutable cursor over a `TinyDeque`, which can also insert and remove elements.
+///
+/// Unlike the `ArrayDeque` cursor, insertion cannot fail, since the deque spills onto the heap.
+pub struct CursorMut<'a, A: Array> {
+    index: usize,
+    deque: &'a mut TinyDeque<A>,
+}
+
+impl<A: Array> CursorMut<'_, A> {
+    /// Get the index of the element the cursor points at, or `None` at the ghost position.
+    #[inline]
+    #[must_use]
+    pub fn index(&self) -> Option<usize> {
+        if self.index < self.deque.len() {
+            Some(self.index)
+        } else {
+            None
+        }
+    }
+
+    /// Move the cursor to the next element, or onto the ghost position from the back element.
+    #[inline]
+    pub fn move_next(&mut self) {
+        self.index = cursor_next(self.index, self.deque.len());
+    }
+
+    /// Move the cursor to the previous element, or onto the ghost position from the front
+    /// element.
+    #[inline]
+    pub fn move_prev(&mut self) {
+        self.index = cursor_prev(self.index, self.deque.len());
+    }
+
+    /// Get the element the cursor points at, or `None` at the ghost position.
+    #[inline]
+    pub fn current(&mut self) -> Option<&mut A::Item> {
+        self.deque.get_mut(self.index)
+    }
+
+    /// Get the element after the cursor. At the ghost position, this is the front element.
+    #[inline]
+    pub fn peek_next(&mut self) -> Option<&mut A::Item> {
+        let next = cursor_next(self.index, self.deque.len());
+        self.deque.get_mut(next)
+    }
+
+    /// Get the element before the cursor. At the ghost position, this is the back element.
+    #[inline]
+    pub fn peek_prev(&mut self) -> Option<&mut A::Item> {
+        let prev = cursor_prev(self.index, self.deque.len());
+        self.deque.get_mut(prev)
+    }
+
+    /// Get a read-only cursor pointing at the same position as this one.
+    #[inline]
+    #[must_use]
+    pub fn as_cursor(&self) -> Cursor<'_, A> {
+        Cursor {
+            index: self.index,
+            deque: self.deque,
+        }
+    }
+
+    /// Insert an element after the cursor. At the ghost position, the element becomes the new
+    /// front element.
+    #[inline]
+    pub fn insert_after(&mut self, element: A::Item) {
+        if self.index < self.deque.len() {
+            self.deque.insert(self.index + 1, element);
+        } else {
+            self.deque.insert(0, element);
+            self.index = self.deque.len();
+        }
+    }
+
+    /// Insert an element before the cursor. At the ghost position, the element becomes the new
+    /// back element.
+    #[inline]
+    pub fn insert_before(&mut self, element: A::Item) {
+        self.deque.insert(self.index, element);
+        self.index += 1;
+    }
+
+    /// Remove the element the cursor points at and move the cursor to the next element.
+    /// Returns `None` at the ghost position.
+    #[inline]
+    pub fn remove_current(&mut self) -> Option<A::Item> {
+        self.deque.remove(self.index)
+    }
+}
+
+#[test]
+fn test_vec_deque_round_trip() {
+    let mut v: VecDeque<u8> = (0..3).collect();
+    v.push_front(9);
+    let tiny = TinyDeque::<[u8; 2]>::from(v);
+    assert!(tiny.iter().copied().eq([9, 0, 1, 2].iter().copied()));
+
+    let stack = TinyDeque::from(ArrayDeque::from([4u8, 5]));
+    assert_eq!(VecDeque::from(stack), [4, 5]);
+}
+
+#[test]
+fn test_cursor_spill() {
+    let mut deque: TinyDeque<[u8; 2]> = TinyDeque::new();
+    let mut cursor = deque.cursor_front_mut();
+    assert_eq!(cursor.index(), None);
+    cursor.insert_after(2);
+    cursor.insert_before(3);
+    cursor.move_next();
+    cursor.insert_after(1);
+    assert_eq!(cursor.as_cursor().peek_prev(), None);
+    cursor.move_next();
+    assert_eq!(cursor.current(), Some(&mut 1));
+    assert_eq!(cursor.peek_prev(), Some(&mut 2));
+    cursor.move_next();
+    assert_eq!(cursor.remove_current(), Some(3));
+    assert_eq!(cursor.index(), None);
+
+    assert!(deque.iter().copied().eq([2, 1].iter().copied()));
+}
+
+#[test]
+fn test_zero_capacity_on_heap() {
+    let mut deque: TinyDeque<[u8; 0]> = TinyDeque::new();
+    assert!(matches!(deque, TinyDeque::Heap(_)));
+    assert!(matches!(
+        TinyDeque::<[u8; 0]>::with_capacity(4),
+        TinyDeque::Heap(_)
+    ));
+
+    deque.push_back(2);
+    deque.push_front(1);
+    deque.cursor_back_mut().insert_before(3);
+    deque.rotate_left(1);
+    assert!(deque.iter().copied().eq([3, 2, 1].iter().copied()));
+    assert_eq!(deque.drain(1..).count(), 2);
+    assert_eq!(deque.pop_front(), Some(3));
+    assert!(deque.is_empty());
+}
@@ -0,0 +1,483 @@
+// MIT/Apache2 License
+
+// the statistics are computed in floating point, so counts are converted to `f64`
+#![allow(clippy::cast_precision_loss)]
+
+use super::array_deque::{ArrayDeque, Iter};
+use core::fmt;
+use tinyvec::Array;
+
+/// An incremental aggregate over the contents of a [`RollingWindow`].
+///
+/// `add` is called for every element that enters the window, and `remove` is called for every
+/// element that is evicted from it.
+pub trait Aggregator<T> {
+    /// Account for an element entering the window.
+    fn add(&mut self, item: &T);
+    /// Account for an element leaving the window.
+    fn remove(&mut self, item: &T);
+}
+
+impl<T> Aggregator<T> for () {
+    #[inline]
+    fn add(&mut self, _item: &T) {}
+    #[inline]
+    fn remove(&mut self, _item: &T) {}
+}
+
+impl<T, G1: Aggregator<T>, G2: Aggregator<T>> Aggregator<T> for (G1, G2) {
+    #[inline]
+    fn add(&mut self, item: &T) {
+        self.0.add(item);
+        self.1.add(item);
+    }
+
+    #[inline]
+    fn remove(&mut self, item: &T) {
+        self.0.remove(item);
+        self.1.remove(item);
+    }
+}
+
+/// Running count, sum, mean and variance.
+///
+/// The sum uses compensated summation, so it does not drift as elements come and go. The mean
+/// and variance are updated using Welford's method, run backwards for removals, which can slowly
+/// pick up rounding error over a very long run. [`RollingWindow`] recomputes its moments once
+/// per lap of the window to keep that from building up.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Moments {
+    count: usize,
+    sum: f64,
+    // the rounding error lost from `sum` so far
+    compensation: f64,
+    mean: f64,
+    m2: f64,
+}
+
+impl Moments {
+    /// Create a new, empty set of moments.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of elements that have been accounted for.
+    #[inline]
+    #[must_use]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The sum of all elements.
+    #[inline]
+    #[must_use]
+    pub fn sum(&self) -> f64 {
+        self.sum + self.compensation
+    }
+
+    /// The arithmetic mean of all elements, or `None` if there are none.
+    #[inline]
+    #[must_use]
+    pub fn mean(&self) -> Option<f64> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(self.mean)
+        }
+    }
+
+    /// The population variance of all elements, or `None` if there are none.
+    #[inline]
+    #[must_use]
+    pub fn variance(&self) -> Option<f64> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(self.m2 / self.count as f64)
+        }
+    }
+
+    /// The sample variance of all elements, or `None` if there are less than two.
+    #[inline]
+    #[must_use]
+    pub fn sample_variance(&self) -> Option<f64> {
+        if self.count < 2 {
+            None
+        } else {
+            Some(self.m2 / (self.count - 1) as f64)
+        }
+    }
+
+    /// Add `x` to the sum, keeping track of the rounding error with Neumaier's variant of Kahan
+    /// summation.
+    #[inline]
+    fn add_to_sum(&mut self, x: f64) {
+        // `f64::abs` is only in `core` from Rust 1.85 on
+        fn abs(x: f64) -> f64 {
+            if x < 0.0 {
+                -x
+            } else {
+                x
+            }
+        }
+
+        let sum = self.sum + x;
+        if abs(self.sum) >= abs(x) {
+            self.compensation += (self.sum - sum) + x;
+        } else {
+            self.compensation += (x - sum) + self.sum;
+        }
+        self.sum = sum;
+    }
+}
+
+impl<T: Copy + Into<f64>> Aggregator<T> for Moments {
+    #[inline]
+    fn add(&mut self, item: &T) {
+        let x: f64 = (*item).into();
+        self.count += 1;
+        self.add_to_sum(x);
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (x - self.mean);
+    }
+
+    #[inline]
+    fn remove(&mut self, item: &T) {
+        if self.count <= 1 {
+            // reset exactly instead of accumulating rounding error
+            *self = Self::default();
+            return;
+        }
+
+        let x: f64 = (*item).into();
+        let old_mean = self.mean;
+        self.count -= 1;
+        self.add_to_sum(-x);
+        // Welford's update run backwards, which does not rescale the mean by the count
+        self.mean -= (x - old_mean) / self.count as f64;
+        self.m2 -= (x - old_mean) * (x - self.mean);
+
+        // rounding can push this slightly under zero
+        if self.m2 < 0.0 {
+            self.m2 = 0.0;
+        }
+    }
+}
+
+/// A fixed-size moving window over an `ArrayDeque` that keeps running statistics.
+///
+/// Once the window is full, pushing a new element evicts the oldest one. The sum, mean and
+/// variance are updated on every push and every eviction, as is the user-provided
+/// [`Aggregator`].
+///
+/// # Example
+///
+/// ```
+/// use tinydeque::window::RollingWindow;
+///
+/// let mut temperatures: RollingWindow<[f32; 3]> = RollingWindow::new();
+/// temperatures.push(20.0);
+/// temperatures.push(22.0);
+/// temperatures.push(24.0);
+/// assert_eq!(temperatures.mean(), Some(22.0));
+///
+/// // the 20 degree reading falls out of the window
+/// assert_eq!(temperatures.push(26.0), Some(20.0));
+/// assert_eq!(temperatures.sum(), 72.0);
+/// assert_eq!(temperatures.mean(), Some(24.0));
+/// ```
+pub struct RollingWindow<A: Array, G = ()> {
+    buffer: ArrayDeque<A>,
+    moments: Moments,
+    aggregator: G,
+    // evictions since the moments were last recomputed from the buffer
+    evictions: usize,
+}
+
+impl<A: Array, G: fmt::Debug> fmt::Debug for RollingWindow<A, G>
+where
+    A::Item: fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RollingWindow")
+            .field("buffer", &self.buffer)
+            .field("moments", &self.moments)
+            .field("aggregator", &self.aggregator)
+            .field("evictions", &self.evictions)
+            .finish()
+    }
+}
+
+impl<A: Array, G: Clone> Clone for RollingWindow<A, G>
+where
+    A::Item: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            buffer: self.buffer.clone(),
+            moments: self.moments,
+            aggregator: self.aggregator.clone(),
+            evictions: self.evictions,
+        }
+    }
+}
+
+impl<A: Array> RollingWindow<A>
+where
+    A::Item: Copy + Into<f64>,
+{
+    /// Create a new, empty `RollingWindow`.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_aggregator(())
+    }
+}
+
+impl<A: Array> Default for RollingWindow<A>
+where
+    A::Item: Copy + Into<f64>,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: Array, G: Aggregator<A::Item>> RollingWindow<A, G>
+where
+    A::Item: Copy + Into<f64>,
+{
+    /// Create a new, empty `RollingWindow` that also maintains the given aggregator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tinydeque::window::{Aggregator, RollingWindow};
+    ///
+    /// /// Counts how many readings in the window are above zero.
+    /// #[derive(Default)]
+    /// struct Positives(usize);
+    ///
+    /// impl Aggregator<i32> for Positives {
+    ///     fn add(&mut self, item: &i32) {
+    ///         if *item > 0 { self.0 += 1; }
+    ///     }
+    ///     fn remove(&mut self, item: &i32) {
+    ///         if *item > 0 { self.0 -= 1; }
+    ///     }
+    /// }
+    ///
+    /// let mut window: RollingWindow<[i32; 2], _> = RollingWindow::with_aggregator(Positives(0));
+    /// window.push(5);
+    /// window.push(-3);
+    /// assert_eq!(window.aggregator().0, 1);
+    /// window.push(-1);
+    /// assert_eq!(window.aggregator().0, 0);
+    /// ```
+    #[inline]
+    pub fn with_aggregator(aggregator: G) -> Self {
+        Self {
+            buffer: ArrayDeque::new(),
+            moments: Moments::new(),
+            aggregator,
+            evictions: 0,
+        }
+    }
+
+    /// The maximum number of elements in the window.
+    #[inline]
+    #[must_use]
+    pub fn capacity() -> usize {
+        A::CAPACITY
+    }
+
+    /// The number of elements currently in the window.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Tell whether this window is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Tell whether this window is full, so the next push will evict an element.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.buffer.is_full()
+    }
+
+    /// Push an element into the window, returning the evicted element if the window was full.
+    #[inline]
+    pub fn push(&mut self, element: A::Item) -> Option<A::Item> {
+        self.moments.add(&element);
+        self.aggregator.add(&element);
+        let evicted = self.buffer.push_back_overwrite(element);
+        if let Some(ref evicted) = evicted {
+            self.evicted(evicted);
+        }
+        evicted
+    }
+
+    /// Evict the oldest element from the window.
+    #[inline]
+    pub fn pop_front(&mut self) -> Option<A::Item> {
+        let evicted = self.buffer.pop_front()?;
+        self.evicted(&evicted);
+        Some(evicted)
+    }
+
+    /// Account for an element that has left the window.
+    #[inline]
+    fn evicted(&mut self, item: &A::Item) {
+        self.moments.remove(item);
+        self.aggregator.remove(item);
+
+        // recompute the moments once per lap, so rounding errors do not build up
+        self.evictions += 1;
+        if self.evictions >= A::CAPACITY {
+            self.evictions = 0;
+            self.moments = Moments::new();
+            for item in &self.buffer {
+                self.moments.add(item);
+            }
+        }
+    }
+
+    /// Evict every element from the window.
+    #[inline]
+    pub fn clear(&mut self) {
+        while let Some(_) = self.pop_front() {}
+    }
+
+    /// The sum of the elements in the window.
+    #[inline]
+    pub fn sum(&self) -> f64 {
+        self.moments.sum()
+    }
+
+    /// The mean of the elements in the window, or `None` if it is empty.
+    #[inline]
+    pub fn mean(&self) -> Option<f64> {
+        self.moments.mean()
+    }
+
+    /// The population variance of the elements in the window, or `None` if it is empty.
+    #[inline]
+    pub fn variance(&self) -> Option<f64> {
+        self.moments.variance()
+    }
+
+    /// The sample variance of the elements in the window, or `None` if it holds less than two.
+    #[inline]
+    pub fn sample_variance(&self) -> Option<f64> {
+        self.moments.sample_variance()
+    }
+
+    /// Get the running moments of the window.
+    #[inline]
+    pub fn moments(&self) -> &Moments {
+        &self.moments
+    }
+
+    /// Get the user-provided aggregator.
+    #[inline]
+    pub fn aggregator(&self) -> &G {
+        &self.aggregator
+    }
+
+    /// Get the underlying `ArrayDeque`, oldest element first.
+    #[inline]
+    pub fn as_deque(&self) -> &ArrayDeque<A> {
+        &self.buffer
+    }
+
+    /// Iterate over the elements in the window, oldest first.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, A> {
+        self.buffer.iter()
+    }
+}
+
+impl<'a, A: Array, G: Aggregator<A::Item>> IntoIterator for &'a RollingWindow<A, G>
+where
+    A::Item: Copy + Into<f64>,
+{
+    type Item = &'a A::Item;
+    type IntoIter = Iter<'a, A>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a, A> {
+        self.iter()
+    }
+}
+
+#[test]
+fn test_rolling_moments() {
+    let samples = [4.0f64, 7.0, 13.0, 16.0, 1.0, -2.0, 9.5, 3.25];
+    let mut window: RollingWindow<[f64; 3]> = RollingWindow::new();
+
+    for (i, &sample) in samples.iter().enumerate() {
+        window.push(sample);
+
+        let start = (i + 1).saturating_sub(3);
+        let expected = &samples[start..=i];
+        let n = expected.len() as f64;
+        let mean = expected.iter().sum::<f64>() / n;
+        let variance = expected
+            .iter()
+            .map(|x| (x - mean) * (x - mean))
+            .sum::<f64>()
+            / n;
+
+        assert!((window.sum() - expected.iter().sum::<f64>()).abs() < 1e-9);
+        assert!((window.mean().unwrap() - mean).abs() < 1e-9);
+        assert!((window.variance().unwrap() - variance).abs() < 1e-9);
+        assert!(window.iter().copied().eq(expected.iter().copied()));
+    }
+
+    window.clear();
+    assert_eq!(window.mean(), None);
+    assert_eq!(window.moments().count(), 0);
+}
+
+#[test]
+fn test_rolling_moments_long_run() {
+    let mut window: RollingWindow<[f64; 8]> = RollingWindow::new();
+    // the same moments, without the window recomputing them
+    let mut running = Moments::new();
+    let mut recent = [0.0f64; 8];
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+
+    for i in 0..100_000 {
+        // a large offset makes cancellation errors show up quickly
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        let sample = 1.0e6 + (state % 1000) as f64 / 7.0;
+        if let Some(evicted) = window.push(sample) {
+            running.remove(&evicted);
+        }
+        running.add(&sample);
+        recent[i % 8] = sample;
+
+        let mut fresh = Moments::new();
+        for x in &recent[..(i + 1).min(8)] {
+            fresh.add(x);
+        }
+
+        let sum: f64 = recent[..(i + 1).min(8)].iter().sum();
+        assert!((window.sum() - sum).abs() < 1e-8);
+        assert!((running.sum() - sum).abs() < 1e-8);
+        assert!((window.mean().unwrap() - fresh.mean().unwrap()).abs() < 1e-6);
+        assert!((window.variance().unwrap() - fresh.variance().unwrap()).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,346 @@
+// MIT/Apache2 License
+
+//! Model-based checking of the deques against `VecDeque`.
+//!
+//! A sequence of operations, usually decoded from fuzzer input, is applied to both the deque
+//! under test and a `VecDeque`, and the two are compared after every step. This module is shared
+//! between the integration tests and the `cargo fuzz` targets.
+
+#![allow(dead_code)]
+
+use arbitrary::Arbitrary;
+use std::{collections::VecDeque, vec::Vec};
+use tinydeque::ArrayDeque;
+use tinyvec::Array;
+
+/// A single operation on a deque. Indices are reduced to the current length before use.
+#[derive(Arbitrary, Debug, Clone)]
+pub enum Op {
+    PushBack(u8),
+    PushFront(u8),
+    PushBackOverwrite(u8),
+    PopBack,
+    PopFront,
+    Insert(u8, u8),
+    Remove(u8),
+    Swap(u8, u8),
+    Set(u8, u8),
+    Truncate(u8),
+    Clear,
+    Drain(u8, u8),
+    RotateLeft(u8),
+    RotateRight(u8),
+    MakeContiguous,
+    Extend(Vec<u8>),
+    ExtendFromSlice(Vec<u8>),
+    ReadInto(Vec<u8>),
+    Clone,
+}
+
+/// Reduce an arbitrary index to `0..=len`.
+fn index(raw: u8, len: usize) -> usize {
+    usize::from(raw) % (len + 1)
+}
+
+/// Reduce an arbitrary pair of indices to a range inside of `0..=len`.
+fn range(a: u8, b: u8, len: usize) -> (usize, usize) {
+    let (a, b) = (index(a, len), index(b, len));
+    (a.min(b), a.max(b))
+}
+
+/// Copy as much of `src` as fits into a pair of spare slices, as a vectored read would.
+fn read_into((front, back): (&mut [u8], &mut [u8]), src: &[u8]) -> usize {
+    let (to_front, rest) = src.split_at(src.len().min(front.len()));
+    let to_back = &rest[..rest.len().min(back.len())];
+    front[..to_front.len()].copy_from_slice(to_front);
+    back[..to_back.len()].copy_from_slice(to_back);
+    to_front.len() + to_back.len()
+}
+
+fn check_contents<'a, I, F>(iter: F, slices: (&[u8], &[u8]), model: &VecDeque<u8>, op: &Op)
+where
+    F: Fn() -> I,
+    I: DoubleEndedIterator<Item = &'a u8> + ExactSizeIterator,
+{
+    assert_eq!(iter().len(), model.len(), "iterator length after {:?}", op);
+    assert!(iter().eq(model.iter()), "contents after {:?}", op);
+    assert!(
+        iter().rev().eq(model.iter().rev()),
+        "reversed contents after {:?}",
+        op
+    );
+
+    let joined: Vec<u8> = slices.0.iter().chain(slices.1).copied().collect();
+    assert!(joined.iter().eq(model.iter()), "as_slices after {:?}", op);
+}
+
+/// Compare an `ArrayDeque` with a `VecDeque` that receives the same operations.
+pub fn check_array_deque<A: Array<Item = u8>>(ops: &[Op]) {
+    let capacity = ArrayDeque::<A>::capacity();
+    let mut deque: ArrayDeque<A> = ArrayDeque::new();
+    let mut model: VecDeque<u8> = VecDeque::new();
+
+    for op in ops {
+        let len = model.len();
+        let full = len == capacity;
+        match *op {
+            Op::PushBack(x) => {
+                let result = deque.try_push_back(x);
+                if full {
+                    assert_eq!(result.unwrap_err().into_inner(), x);
+                } else {
+                    assert!(result.is_ok());
+                    model.push_back(x);
+                }
+            }
+            Op::PushFront(x) => {
+                let result = deque.try_push_front(x);
+                if full {
+                    assert_eq!(result.unwrap_err().into_inner(), x);
+                } else {
+                    assert!(result.is_ok());
+                    model.push_front(x);
+                }
+            }
+            Op::PushBackOverwrite(x) => {
+                let evicted = if capacity == 0 {
+                    Some(x)
+                } else {
+                    let evicted = if full { model.pop_front() } else { None };
+                    model.push_back(x);
+                    evicted
+                };
+                assert_eq!(deque.push_back_overwrite(x), evicted);
+            }
+            Op::PopBack => assert_eq!(deque.pop_back(), model.pop_back()),
+            Op::PopFront => assert_eq!(deque.pop_front(), model.pop_front()),
+            Op::Insert(i, x) => {
+                let i = index(i, len);
+                let result = deque.try_insert(i, x);
+                if full {
+                    assert_eq!(result.unwrap_err().into_inner(), x);
+                } else {
+                    assert!(result.is_ok());
+                    model.insert(i, x);
+                }
+            }
+            Op::Remove(i) => {
+                let i = index(i, len);
+                assert_eq!(deque.remove(i), model.remove(i));
+            }
+            Op::Swap(i, j) => {
+                if len > 0 {
+                    let (i, j) = (usize::from(i) % len, usize::from(j) % len);
+                    deque.swap(i, j);
+                    model.swap(i, j);
+                }
+            }
+            Op::Set(i, x) => {
+                let i = index(i, len);
+                if let Some(slot) = model.get_mut(i) {
+                    *slot = x;
+                }
+                if let Some(slot) = deque.get_mut(i) {
+                    *slot = x;
+                }
+            }
+            Op::Truncate(n) => {
+                let n = usize::from(n) % (len + 2);
+                deque.truncate(n);
+                model.truncate(n);
+            }
+            Op::Clear => {
+                deque.clear();
+                model.clear();
+            }
+            Op::Drain(a, b) => {
+                let (a, b) = range(a, b, len);
+                assert!(
+                    deque.drain(a..b).eq(model.drain(a..b)),
+                    "drained {}..{}",
+                    a,
+                    b
+                );
+            }
+            Op::RotateLeft(n) => {
+                let n = index(n, len);
+                deque.rotate_left(n);
+                model.rotate_left(n);
+            }
+            Op::RotateRight(n) => {
+                let n = index(n, len);
+                deque.rotate_right(n);
+                model.rotate_right(n);
+            }
+            Op::MakeContiguous => {
+                assert_eq!(deque.make_contiguous(), model.make_contiguous());
+                assert!(deque.is_contiguous());
+            }
+            Op::Extend(ref items) => {
+                let fits = items.len() <= capacity - len;
+                let result = deque.try_extend(items.iter().copied());
+                assert_eq!(
+                    result.is_ok(),
+                    fits,
+                    "extend by {} with {} free",
+                    items.len(),
+                    capacity - len
+                );
+                model.extend(items.iter().copied().take(capacity - len));
+            }
+            Op::ReadInto(ref items) => {
+                let read = read_into(deque.spare_capacity_mut(), items);
+                assert_eq!(read, items.len().min(capacity - len));
+                deque.commit_back(read);
+                model.extend(&items[..read]);
+            }
+            Op::ExtendFromSlice(ref items) => {
+                let fits = items.len() <= capacity - len;
+                assert_eq!(deque.try_extend_from_slice(items).is_ok(), fits);
+                if fits {
+                    model.extend(items.iter().copied());
+                }
+            }
+            Op::Clone => deque = deque.clone(),
+        }
+
+        assert_eq!(deque.len(), model.len(), "length after {:?}", op);
+        assert_eq!(
+            deque.is_empty(),
+            model.is_empty(),
+            "is_empty after {:?}",
+            op
+        );
+        assert_eq!(
+            deque.is_full(),
+            model.len() == capacity,
+            "is_full after {:?}",
+            op
+        );
+        assert_eq!(deque.front(), model.front(), "front after {:?}", op);
+        assert_eq!(deque.back(), model.back(), "back after {:?}", op);
+        for i in 0..=model.len() {
+            assert_eq!(deque.get(i), model.get(i), "index {} after {:?}", i, op);
+        }
+        check_contents(|| deque.iter(), deque.as_slices(), &model, op);
+    }
+}
+
+/// Compare a `TinyDeque` with a `VecDeque` that receives the same operations.
+#[cfg(feature = "alloc")]
+pub fn check_tiny_deque<A: Array<Item = u8>>(ops: &[Op]) {
+    use tinydeque::TinyDeque;
+
+    let mut deque: TinyDeque<A> = TinyDeque::new();
+    let mut model: VecDeque<u8> = VecDeque::new();
+
+    for op in ops {
+        let len = model.len();
+        match *op {
+            Op::PushBack(x) => {
+                deque.push_back(x);
+                model.push_back(x);
+            }
+            Op::PushFront(x) => {
+                deque.push_front(x);
+                model.push_front(x);
+            }
+            // a `TinyDeque` spills instead of filling up, so nothing is ever overwritten
+            Op::PushBackOverwrite(x) => {
+                deque.push_back(x);
+                model.push_back(x);
+            }
+            Op::PopBack => assert_eq!(deque.pop_back(), model.pop_back()),
+            Op::PopFront => assert_eq!(deque.pop_front(), model.pop_front()),
+            Op::Insert(i, x) => {
+                let i = index(i, len);
+                deque.insert(i, x);
+                model.insert(i, x);
+            }
+            Op::Remove(i) => {
+                let i = index(i, len);
+                assert_eq!(deque.remove(i), model.remove(i));
+            }
+            Op::Swap(i, j) => {
+                if len > 0 {
+                    let (i, j) = (usize::from(i) % len, usize::from(j) % len);
+                    deque.swap(i, j);
+                    model.swap(i, j);
+                }
+            }
+            Op::Set(i, x) => {
+                let i = index(i, len);
+                if let Some(slot) = model.get_mut(i) {
+                    *slot = x;
+                }
+                if let Some(slot) = deque.get_mut(i) {
+                    *slot = x;
+                }
+            }
+            Op::Truncate(n) => {
+                let n = usize::from(n) % (len + 2);
+                deque.truncate(n);
+                model.truncate(n);
+            }
+            Op::Clear => {
+                deque.clear();
+                model.clear();
+            }
+            Op::Drain(a, b) => {
+                let (a, b) = range(a, b, len);
+                assert!(
+                    deque.drain(a..b).eq(model.drain(a..b)),
+                    "drained {}..{}",
+                    a,
+                    b
+                );
+            }
+            Op::RotateLeft(n) => {
+                let n = index(n, len);
+                deque.rotate_left(n);
+                model.rotate_left(n);
+            }
+            Op::RotateRight(n) => {
+                let n = index(n, len);
+                deque.rotate_right(n);
+                model.rotate_right(n);
+            }
+            Op::ReadInto(ref items) => {
+                // a spilled `TinyDeque` has no spare slots to read into, so push instead
+                let read = match deque.spare_capacity_mut() {
+                    Some(spare) => {
+                        let read = read_into(spare, items);
+                        deque.commit_back(read);
+                        read
+                    }
+                    None => {
+                        deque.extend(items.iter().copied());
+                        items.len()
+                    }
+                };
+                model.extend(&items[..read]);
+            }
+            Op::MakeContiguous => {
+                assert_eq!(deque.make_contiguous(), model.make_contiguous());
+                assert!(deque.as_slices().1.is_empty());
+            }
+            Op::Extend(ref items) | Op::ExtendFromSlice(ref items) => {
+                items.iter().for_each(|&x| deque.push_back(x));
+                model.extend(items.iter().copied());
+            }
+            Op::Clone => deque = deque.clone(),
+        }
+
+        assert_eq!(deque.len(), model.len(), "length after {:?}", op);
+        assert_eq!(
+            deque.is_empty(),
+            model.is_empty(),
+            "is_empty after {:?}",
+            op
+        );
+        for i in 0..=model.len() {
+            assert_eq!(deque.get(i), model.get(i), "index {} after {:?}", i, op);
+        }
+        check_contents(|| deque.iter(), deque.as_slices(), &model, op);
+    }
+}
@@ -0,0 +1,50 @@
+// MIT/Apache2 License
+
+//! Run random operation sequences against `VecDeque`, using the same model as the fuzz targets.
+
+mod model;
+
+use arbitrary::Unstructured;
+use model::Op;
+use std::vec::Vec;
+
+const SEEDS: u64 = 400;
+
+/// Generate a reproducible sequence of operations from a seed.
+fn ops(seed: u64) -> Vec<Op> {
+    // splitmix64, so no dependency on a random number generator is needed
+    let mut state = seed;
+    let bytes: Vec<u8> = (0..512)
+        .flat_map(|_| {
+            state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            (z ^ (z >> 31)).to_le_bytes()
+        })
+        .collect();
+    Unstructured::new(&bytes).arbitrary().unwrap()
+}
+
+#[test]
+fn test_array_deque_matches_vec_deque() {
+    for seed in 0..SEEDS {
+        let ops = ops(seed);
+        model::check_array_deque::<[u8; 0]>(&ops);
+        model::check_array_deque::<[u8; 1]>(&ops);
+        model::check_array_deque::<[u8; 4]>(&ops);
+        model::check_array_deque::<[u8; 7]>(&ops);
+        model::check_array_deque::<[u8; 16]>(&ops);
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_tiny_deque_matches_vec_deque() {
+    for seed in 0..SEEDS {
+        let ops = ops(seed);
+        model::check_tiny_deque::<[u8; 0]>(&ops);
+        model::check_tiny_deque::<[u8; 1]>(&ops);
+        model::check_tiny_deque::<[u8; 5]>(&ops);
+    }
+}